@@ -0,0 +1,42 @@
+/// Generates the module list of the in-process `all` binary from the solutions in `src/bin`.
+use std::{env, fs, path::Path};
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/bin");
+
+    let bin_dir = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("src/bin");
+
+    let mut bins: Vec<String> = fs::read_dir(&bin_dir)
+        .unwrap()
+        .filter_map(|entry| {
+            let name = entry.ok()?.file_name().into_string().ok()?;
+            let bin = name.strip_suffix(".rs")?;
+            // days of the default year are named `01`, days of other years `2023_01`.
+            let is_day = match bin.split_once('_') {
+                Some((year, day)) => is_number(year, 4) && is_number(day, 2),
+                None => is_number(bin, 2),
+            };
+            is_day.then(|| bin.to_string())
+        })
+        .collect();
+    bins.sort_unstable();
+
+    let modules: String = bins
+        .iter()
+        .map(|bin| {
+            let path = bin_dir.join(format!("{bin}.rs"));
+            // the tests of every day already run with its own binary, not again with `all`.
+            format!(
+                "#[cfg(not(test))]\n#[allow(dead_code)]\n#[path = {:?}]\nmod day_{bin};\n",
+                path.display().to_string()
+            )
+        })
+        .collect();
+
+    let out_path = Path::new(&env::var("OUT_DIR").unwrap()).join("solutions.rs");
+    fs::write(out_path, modules).unwrap();
+}
+
+fn is_number(s: &str, len: usize) -> bool {
+    s.len() == len && s.bytes().all(|b| b.is_ascii_digit())
+}
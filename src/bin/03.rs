@@ -1,6 +1,6 @@
 advent_of_code::solution!(3);
 
-use regex::Regex;
+use advent_of_code::parse::{Cursor, ParseError};
 
 #[derive(Debug, Clone, Copy)]
 enum Operation {
@@ -9,23 +9,40 @@ enum Operation {
     Dont,
 }
 
-fn get_operations(haystack: &str, re: &str) -> Vec<Operation> {
-    let re = Regex::new(re).unwrap();
-    let mut operations = Vec::new();
-    for needle in re.captures_iter(haystack) {
-        if let Some(left) = needle.get(1) {
-            let left = left.as_str().parse::<u32>().unwrap();
-            let right = needle.get(2).unwrap().as_str().parse::<u32>().unwrap();
-            operations.push(Operation::Multiply(left, right));
-        } else if needle.get(3).is_some() {
-            operations.push(Operation::Do);
-        } else if needle.get(4).is_some() {
-            operations.push(Operation::Dont);
-        } else {
-            panic!("unexpected capture");
-        }
+/// `mul(x,y)` with operands of up to `max_digits` digits.
+fn parse_multiply(c: &mut Cursor, max_digits: usize) -> Result<Operation, ParseError> {
+    c.tag("mul(")?;
+    let left = c.digits(1..=max_digits)?;
+    c.tag(",")?;
+    let right = c.digits(1..=max_digits)?;
+    c.tag(")")?;
+    // at most 4 digits always fit.
+    Ok(Operation::Multiply(
+        left.parse().unwrap(),
+        right.parse().unwrap(),
+    ))
+}
+
+fn parse_condition(c: &mut Cursor) -> Result<Operation, ParseError> {
+    if c.attempt(|c| c.tag("do()")).is_some() {
+        return Ok(Operation::Do);
     }
-    operations
+    c.tag("don't()")?;
+    Ok(Operation::Dont)
+}
+
+fn get_operations(haystack: &str, with_conditions: bool) -> Vec<Operation> {
+    let max_digits = if with_conditions { 4 } else { 3 };
+    Cursor::new(haystack)
+        .scan(move |c| {
+            if with_conditions {
+                if let Some(operation) = c.attempt(parse_condition) {
+                    return Ok(operation);
+                }
+            }
+            parse_multiply(c, max_digits)
+        })
+        .collect()
 }
 
 fn filter_operations(operations: &Vec<Operation>) -> Vec<(u32, u32)> {
@@ -45,20 +62,18 @@ fn filter_operations(operations: &Vec<Operation>) -> Vec<(u32, u32)> {
     ret_val
 }
 
-fn calculate_result(haystack: &str, re: &str) -> u32 {
-    let operations = get_operations(haystack, re);
+fn calculate_result(haystack: &str, with_conditions: bool) -> u32 {
+    let operations = get_operations(haystack, with_conditions);
     let operations = filter_operations(&operations);
     operations.iter().map(|o| o.0 * o.1).sum()
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let re = r"mul\((\d{1,3}),(\d{1,3})\)";
-    Some(calculate_result(input, re))
+    Some(calculate_result(input, false))
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let re = r"mul\((\d{1,4}),(\d{1,4})\)|(do\(\))|(don't\(\))";
-    Some(calculate_result(input, re))
+    Some(calculate_result(input, true))
 }
 
 #[cfg(test)]
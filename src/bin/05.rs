@@ -1,40 +1,26 @@
 advent_of_code::solution!(5);
 
-use regex::Regex;
+use advent_of_code::parse::{self, Cursor, ParseError};
 
-fn parse_rules(input: &str) -> Vec<(u32, u32)> {
-    let re = r"(\d+)\|(\d+)";
-    let re = Regex::new(re).unwrap();
-    let mut rules = Vec::new();
+type Rule = (u32, u32);
 
-    for needles in re.captures_iter(input) {
-        let Ok(left) = needles[1].parse::<u32>() else {
-            continue;
-        };
-        let Ok(right) = needles[2].parse::<u32>() else {
-            continue;
-        };
-        rules.push((left, right));
-    }
-    rules
-}
+fn parse_input(input: &str) -> Result<(Vec<Rule>, Vec<Vec<u32>>), ParseError> {
+    let mut sections = parse::sections(input);
+    let rules = sections.next().unwrap_or_default();
+    let updates = sections.next().unwrap_or_default();
 
-fn parse_update(input: &str) -> Vec<u32> {
-    let re = r"(\d+)";
-    let re = Regex::new(re).unwrap();
-    re.captures_iter(input)
-        .map(|c| c[1].parse::<u32>().unwrap())
-        .collect()
-}
-fn parse_updates(input: &str) -> Vec<Vec<u32>> {
-    let re = r"(.{6,})";
-    let re = Regex::new(re).unwrap();
-    re.captures_iter(input)
-        .map(|c| parse_update(&c[1]))
-        .collect()
+    let rules = parse::lines(rules, |line| parse::pair(line, "|")).collect::<Result<_, _>>()?;
+    let updates = parse::lines(updates, |line| {
+        let mut cursor = Cursor::new(line);
+        let pages = cursor.separated(",", Cursor::uint)?;
+        cursor.end()?;
+        Ok(pages)
+    })
+    .collect::<Result<_, _>>()?;
+    Ok((rules, updates))
 }
 
-fn get_valid_updates(rules: &[(u32, u32)], updates: &[Vec<u32>]) -> Vec<Vec<u32>> {
+fn get_valid_updates(rules: &[Rule], updates: &[Vec<u32>]) -> Vec<Vec<u32>> {
     updates
         .iter()
         .filter(|u| get_invalid_index(rules, u).is_none())
@@ -42,7 +28,7 @@ fn get_valid_updates(rules: &[(u32, u32)], updates: &[Vec<u32>]) -> Vec<Vec<u32>
         .collect()
 }
 
-fn get_invalid_updates(rules: &[(u32, u32)], updates: &[Vec<u32>]) -> Vec<Vec<u32>> {
+fn get_invalid_updates(rules: &[Rule], updates: &[Vec<u32>]) -> Vec<Vec<u32>> {
     updates
         .iter()
         .filter(|u| get_invalid_index(rules, u).is_some())
@@ -50,7 +36,7 @@ fn get_invalid_updates(rules: &[(u32, u32)], updates: &[Vec<u32>]) -> Vec<Vec<u3
         .collect()
 }
 
-fn get_invalid_index(rules: &[(u32, u32)], update: &[u32]) -> Option<(usize, u32)> {
+fn get_invalid_index(rules: &[Rule], update: &[u32]) -> Option<(usize, u32)> {
     for (index, value) in update.iter().enumerate() {
         let update = &update[index..];
         if rules
@@ -66,7 +52,7 @@ fn get_invalid_index(rules: &[(u32, u32)], update: &[u32]) -> Option<(usize, u32
     None
 }
 
-fn reorder_update(rules: &[(u32, u32)], update: &mut [u32]) {
+fn reorder_update(rules: &[Rule], update: &mut [u32]) {
     let Some((invalid_index, invalid_value)) = get_invalid_index(rules, update) else {
         return; // in order
     };
@@ -82,25 +68,23 @@ fn reorder_update(rules: &[(u32, u32)], update: &mut [u32]) {
     update.swap(invalid_index, swap_index);
     reorder_update(rules, update);
 }
-fn reorder_updates(rules: &[(u32, u32)], updates: &mut [Vec<u32>]) {
+fn reorder_updates(rules: &[Rule], updates: &mut [Vec<u32>]) {
     for update in updates.iter_mut() {
         reorder_update(rules, update);
     }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let rules = parse_rules(input);
-    let updates = parse_updates(input);
+pub fn part_one(input: &str) -> Result<u32, ParseError> {
+    let (rules, updates) = parse_input(input)?;
     let updates = get_valid_updates(&rules, &updates);
-    Some(updates.iter().map(|u| u[u.len() / 2]).sum())
+    Ok(updates.iter().map(|u| u[u.len() / 2]).sum())
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let rules = parse_rules(input);
-    let updates = parse_updates(input);
+pub fn part_two(input: &str) -> Result<u32, ParseError> {
+    let (rules, updates) = parse_input(input)?;
     let mut updates = get_invalid_updates(&rules, &updates);
     reorder_updates(&rules, &mut updates);
-    Some(updates.iter().map(|u| u[u.len() / 2]).sum())
+    Ok(updates.iter().map(|u| u[u.len() / 2]).sum())
 }
 
 #[cfg(test)]
@@ -110,12 +94,12 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(143));
+        assert_eq!(result, Ok(143));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(123));
+        assert_eq!(result, Ok(123));
     }
 }
@@ -1,5 +1,7 @@
 advent_of_code::solution!(6);
 
+use advent_of_code::grid::{Grid, Position};
+use advent_of_code::point::Dir4;
 use itertools::Itertools;
 
 #[derive(Debug, Copy, Clone, Eq, Hash, PartialEq)]
@@ -20,117 +22,40 @@ impl State {
     }
 }
 
-#[derive(Debug, Copy, Clone, Eq, Hash, PartialEq)]
-enum Direction {
-    Left,
-    Right,
-    Up,
-    Down,
-}
-
-impl Direction {
-    fn turn_clockwise(&self) -> Direction {
-        match self {
-            Direction::Left => Direction::Up,
-            Direction::Up => Direction::Right,
-            Direction::Right => Direction::Down,
-            Direction::Down => Direction::Left,
-        }
-    }
-}
-
-#[derive(Debug, Copy, Clone, Eq, Hash, PartialEq)]
-struct Coordinate {
-    x: usize,
-    y: usize,
-}
-
-impl Coordinate {
-    fn new(x: usize, y: usize) -> Coordinate {
-        Coordinate { x, y }
-    }
-}
-
-#[derive(Debug, Clone, Eq, Hash, PartialEq)]
-struct Map {
-    map: Vec<Vec<State>>,
-}
-
 #[derive(Debug, Copy, Clone, Eq, Hash, PartialEq)]
 struct Status {
-    coordinate: Coordinate,
-    direction: Direction,
+    coordinate: Position,
+    direction: Dir4,
 }
 
 impl Status {
-    fn new(coordinate: Coordinate, direction: Direction) -> Status {
+    fn new(coordinate: Position, direction: Dir4) -> Status {
         Status {
             coordinate,
             direction,
         }
     }
 
-    fn next_status(&self) -> Option<Status> {
-        match self.direction {
-            Direction::Left if self.coordinate.x != 0usize => Some(Status::new(
-                Coordinate::new(self.coordinate.x - 1usize, self.coordinate.y),
-                self.direction,
-            )),
-            Direction::Right if self.coordinate.x != usize::MAX => Some(Status::new(
-                Coordinate::new(self.coordinate.x + 1usize, self.coordinate.y),
-                self.direction,
-            )),
-            Direction::Up if self.coordinate.y != 0usize => Some(Status::new(
-                Coordinate::new(self.coordinate.x, self.coordinate.y - 1usize),
-                self.direction,
-            )),
-            Direction::Down if self.coordinate.y != usize::MAX => Some(Status::new(
-                Coordinate::new(self.coordinate.x, self.coordinate.y + 1usize),
-                self.direction,
-            )),
-            _ => None,
-        }
+    fn next_status(&self, map: &Map) -> Option<Status> {
+        let coordinate = map.step(self.coordinate, self.direction.delta().into())?;
+        Some(Status::new(coordinate, self.direction))
     }
 
     fn turn_clockwise(&self) -> Status {
-        Status::new(self.coordinate, self.direction.turn_clockwise())
+        Status::new(self.coordinate, self.direction.turn_right())
     }
 }
 
-impl Map {
-    fn new(map: Vec<Vec<State>>) -> Map {
-        Map { map }
-    }
-
-    fn get_guard_starting_position(&self) -> Option<Coordinate> {
-        for (y, row) in self.map.iter().enumerate() {
-            for (x, state) in row.iter().enumerate() {
-                if *state == State::GuardStartingPosition {
-                    return Some(Coordinate::new(x, y));
-                }
-            }
-        }
-        None
-    }
+type Map = Grid<State>;
 
-    fn get_state(&self, coord: &Coordinate) -> Option<&State> {
-        self.map.get(coord.y).and_then(|row| row.get(coord.x))
-    }
-
-    fn place_obstacle(&mut self, coord: &Coordinate) {
-        self.map[coord.y][coord.x] = State::Obstacle;
-    }
-}
-fn read_line(line: &str) -> Vec<State> {
-    line.chars()
-        .map(|c| State::from(&c).expect("All characters in line can be parsed to a state"))
-        .collect::<Vec<_>>()
-}
-fn read_map(input: &str) -> Vec<Vec<State>> {
-    input.lines().map(read_line).collect()
+fn read_map(input: &str) -> Map {
+    Grid::parse(input, |c| {
+        State::from(&c).expect("All characters in line can be parsed to a state")
+    })
+    .expect("map to be a rectangular grid")
 }
 
-fn get_unique_coordinates(prev_moves: &[Status]) -> Vec<Coordinate> {
+fn get_unique_coordinates(prev_moves: &[Status]) -> Vec<Position> {
     prev_moves
         .iter()
         .map(|&s| s.coordinate)
@@ -147,14 +72,14 @@ fn register_status_in_log(prev_moves: &mut Vec<Status>, status: Status) {
 }
 
 fn move_guard_forward(map: &Map, guard_status: Status) -> Option<Status> {
-    let next_status = guard_status.next_status()?;
-    let next_coordinate_state = map.get_state(&next_status.coordinate)?;
+    let next_status = guard_status.next_status(map)?;
+    let next_coordinate_state = &map[next_status.coordinate];
     if next_coordinate_state == &State::Obstacle {
         return move_guard_forward(map, guard_status.turn_clockwise());
     }
     Some(next_status)
 }
-fn trace_guard_path(map: &Map, init_status: Status) -> Option<Vec<Coordinate>> {
+fn trace_guard_path(map: &Map, init_status: Status) -> Option<Vec<Position>> {
     let mut guard_status = init_status;
     let mut prev_moves = Vec::new();
 
@@ -171,13 +96,13 @@ fn trace_guard_path(map: &Map, init_status: Status) -> Option<Vec<Coordinate>> {
     }
 }
 
-fn place_obstacle(map: &Map, coordinate: &Coordinate) -> Map {
+fn place_obstacle(map: &Map, coordinate: &Position) -> Map {
     let mut new_map = map.clone();
-    new_map.place_obstacle(coordinate);
+    new_map[*coordinate] = State::Obstacle;
     new_map
 }
 
-fn get_infinite_loops(map: &Map, init_status: Status, prev_path: &[Coordinate]) -> u32 {
+fn get_infinite_loops(map: &Map, init_status: Status, prev_path: &[Position]) -> u32 {
     let mut count = 0;
     for coordinate in prev_path.iter().filter(|p| init_status.coordinate != **p) {
         // NOTE: big slowdown here due to copying the map each time.
@@ -193,21 +118,21 @@ fn get_infinite_loops(map: &Map, init_status: Status, prev_path: &[Coordinate])
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let map = Map::new(read_map(input));
+    let map = read_map(input);
     let init_guard_coordinate = map
-        .get_guard_starting_position()
+        .find(&State::GuardStartingPosition)
         .expect("map to start with a guard on it");
-    let init_guard_status = Status::new(init_guard_coordinate, Direction::Up);
+    let init_guard_status = Status::new(init_guard_coordinate, Dir4::Up);
     let path = trace_guard_path(&map, init_guard_status).expect("finite path");
     Some(path.len() as u32)
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let map = Map::new(read_map(input));
+    let map = read_map(input);
     let init_guard_coordinate = map
-        .get_guard_starting_position()
+        .find(&State::GuardStartingPosition)
         .expect("map to start with a guard on it");
-    let init_guard_status = Status::new(init_guard_coordinate, Direction::Up);
+    let init_guard_status = Status::new(init_guard_coordinate, Dir4::Up);
     let path = trace_guard_path(&map, init_guard_status).expect("finite path");
     let infinite_loop_count = get_infinite_loops(&map, init_guard_status, &path);
     Some(infinite_loop_count)
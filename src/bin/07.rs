@@ -1,5 +1,7 @@
 advent_of_code::solution!(7);
 
+use advent_of_code::parse::{self, ParseError};
+
 enum Operation {
     Multiply,
     Add,
@@ -17,7 +19,7 @@ impl Operation {
 
     fn reverse_operation(&self, result: u64, value: u64) -> Option<u64> {
         match self {
-            Operation::Multiply => (result % value == 0).then(|| result / value),
+            Operation::Multiply => result.is_multiple_of(value).then(|| result / value),
             Operation::Add => result.checked_sub(value),
             Operation::Concatenate => {
                 let value_digit_count = (value as f64).log10().floor() as u64 + 1;
@@ -34,15 +36,13 @@ impl Operation {
         }
     }
 }
-fn read_equation(line: &str) -> (u64, Vec<u64>) {
-    let mut parts = line.split(|c: char| !c.is_numeric());
-    let result = parts.next().unwrap().parse::<u64>().unwrap();
-    let values = parts.filter_map(|s| s.parse().ok()).collect::<Vec<u64>>();
-    (result, values)
+fn read_equation(line: &str) -> Result<(u64, Vec<u64>), ParseError> {
+    let (result, values) = parse::key_values(line)?;
+    Ok((result, values.collect::<Result<_, _>>()?))
 }
 
-fn read_equations(input: &str) -> Vec<(u64, Vec<u64>)> {
-    input.lines().map(read_equation).collect()
+fn read_equations(input: &str) -> Result<Vec<(u64, Vec<u64>)>, ParseError> {
+    parse::lines(input, read_equation).collect()
 }
 
 fn equation_is_solvable_1(result: u64, values: &[u64]) -> bool {
@@ -72,22 +72,22 @@ fn equation_is_solvable_2(result: u64, values: &[u64]) -> bool {
         .any(|r| equation_is_solvable_2(r, remaining_operands))
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let equations = read_equations(input);
+pub fn part_one(input: &str) -> Result<u64, ParseError> {
+    let equations = read_equations(input)?;
     let solvable_equations = equations
         .iter()
         .filter(|(r, v)| equation_is_solvable_1(*r, v.as_slice()));
     let sum = solvable_equations.map(|s| s.0).sum::<u64>();
-    Some(sum)
+    Ok(sum)
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let equations = read_equations(input);
+pub fn part_two(input: &str) -> Result<u64, ParseError> {
+    let equations = read_equations(input)?;
     let solvable_equations = equations
         .iter()
         .filter(|(r, v)| equation_is_solvable_2(*r, v.as_slice()));
     let sum = solvable_equations.map(|s| s.0).sum::<u64>();
-    Some(sum)
+    Ok(sum)
 }
 
 #[cfg(test)]
@@ -97,12 +97,12 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(3749));
+        assert_eq!(result, Ok(3749));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(11387));
+        assert_eq!(result, Ok(11387));
     }
 }
@@ -0,0 +1,12 @@
+/// Counts the allocations of every day, as the days themselves can't install an allocator in here.
+#[cfg(all(feature = "alloc-stats", not(feature = "dhat-heap")))]
+#[global_allocator]
+static ALLOC: advent_of_code::template::alloc_stats::CountingAlloc =
+    advent_of_code::template::alloc_stats::CountingAlloc;
+
+include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+
+/// Runs every solution in `src/bin` within a single process. Requires the `registry` feature.
+fn main() {
+    advent_of_code::template::registry::run_all();
+}
@@ -0,0 +1,405 @@
+/// A two-dimensional grid backed by a flat vector, as found in many puzzle inputs.
+use std::error::Error;
+use std::fmt::Display;
+
+use crate::point::Point;
+
+/// A position in a [`Grid`], given as `(x, y)` with the origin in the upper left corner.
+pub type Position = (usize, usize);
+
+/// Offsets of the four orthogonal neighbours (up, right, down, left).
+pub const OFFSETS_4: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// Offsets of all eight neighbours, clockwise starting from up.
+pub const OFFSETS_8: [(isize, isize); 8] = [
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
+
+/// A rectangular grid of cells of type `T`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid from row-major `cells`, returns [`None`] if the cell count does not match the dimensions.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Option<Self> {
+        if cells.len() != width * height {
+            return None;
+        }
+        Some(Self {
+            cells,
+            width,
+            height,
+        })
+    }
+
+    /// Parses a grid from lines of text, mapping every character to a cell with `f`.
+    ///
+    /// Fails if the input is empty or the lines are not all the same length.
+    pub fn parse(input: &str, mut f: impl FnMut(char) -> T) -> Result<Self, ParseGridError> {
+        let mut cells = vec![];
+        let mut width = None;
+        let mut height = 0;
+
+        for (y, line) in input.lines().enumerate() {
+            let len_before = cells.len();
+            cells.extend(line.chars().map(&mut f));
+            let len = cells.len() - len_before;
+
+            match width {
+                None => width = Some(len),
+                Some(w) if w != len => {
+                    return Err(ParseGridError::RaggedLine {
+                        line: y + 1,
+                        expected: w,
+                        found: len,
+                    })
+                }
+                Some(_) => {}
+            }
+            height += 1;
+        }
+
+        match width {
+            Some(width) if width > 0 => Ok(Self {
+                cells,
+                width,
+                height,
+            }),
+            _ => Err(ParseGridError::Empty),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The exclusive upper bound of positions, for use with [`Point::checked_step`].
+    pub fn bounds(&self) -> Point<usize> {
+        Point::new(self.width, self.height)
+    }
+
+    /// Returns `true` if `pos` lies within the grid.
+    pub fn contains(&self, pos: Position) -> bool {
+        pos.0 < self.width && pos.1 < self.height
+    }
+
+    /// Returns `true` if the signed position `(x, y)` lies within the grid.
+    pub fn contains_signed(&self, x: isize, y: isize) -> bool {
+        x >= 0 && y >= 0 && self.contains((x as usize, y as usize))
+    }
+
+    pub fn get(&self, pos: Position) -> Option<&T> {
+        if self.contains(pos) {
+            self.cells.get(self.index(pos))
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, pos: Position) -> Option<&mut T> {
+        if self.contains(pos) {
+            let index = self.index(pos);
+            self.cells.get_mut(index)
+        } else {
+            None
+        }
+    }
+
+    /// Replaces the cell at `pos`, returns the previous value or [`None`] if `pos` is out of bounds.
+    pub fn set(&mut self, pos: Position, value: T) -> Option<T> {
+        self.get_mut(pos).map(|cell| std::mem::replace(cell, value))
+    }
+
+    /// Moves `pos` by `(dx, dy)`, returns [`None`] if the result falls outside the grid.
+    pub fn step(&self, pos: Position, (dx, dy): (isize, isize)) -> Option<Position> {
+        let x = pos.0.checked_add_signed(dx)?;
+        let y = pos.1.checked_add_signed(dy)?;
+        self.contains((x, y)).then_some((x, y))
+    }
+
+    /// Iterates all positions in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |i| (i % width, i / width))
+    }
+
+    /// Iterates all cells together with their positions in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// Iterates the cells of row `y`. Yields nothing if `y` is out of bounds.
+    pub fn row(&self, y: usize) -> impl Iterator<Item = &T> {
+        let range = if y < self.height {
+            y * self.width..(y + 1) * self.width
+        } else {
+            0..0
+        };
+        self.cells[range].iter()
+    }
+
+    /// Iterates the cells of column `x`. Yields nothing if `x` is out of bounds.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        let height = if x < self.width { self.height } else { 0 };
+        (0..height).map(move |y| &self.cells[y * self.width + x])
+    }
+
+    /// Iterates the rows as slices, yields nothing for a grid without columns.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // a grid of width 0 has no cells, and `chunks` panics on a chunk size of 0.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// Iterates the positions starting at (and including) `start`, stepping by `delta` until leaving the grid.
+    pub fn ray(
+        &self,
+        start: Position,
+        delta: (isize, isize),
+    ) -> impl Iterator<Item = Position> + '_ {
+        std::iter::successors(self.contains(start).then_some(start), move |&pos| {
+            self.step(pos, delta)
+        })
+    }
+
+    /// Iterates the cells on the diagonal running down and to the right from `start`.
+    pub fn diagonal(&self, start: Position) -> impl Iterator<Item = &T> {
+        self.ray(start, (1, 1))
+            .map(|pos| &self.cells[self.index(pos)])
+    }
+
+    /// Iterates the cells on the anti-diagonal running down and to the left from `start`.
+    pub fn anti_diagonal(&self, start: Position) -> impl Iterator<Item = &T> {
+        self.ray(start, (-1, 1))
+            .map(|pos| &self.cells[self.index(pos)])
+    }
+
+    /// Iterates the in-bounds orthogonal neighbours of `pos`.
+    pub fn neighbours_4(&self, pos: Position) -> impl Iterator<Item = Position> + '_ {
+        OFFSETS_4
+            .iter()
+            .filter_map(move |&delta| self.step(pos, delta))
+    }
+
+    /// Iterates the in-bounds orthogonal and diagonal neighbours of `pos`.
+    pub fn neighbours_8(&self, pos: Position) -> impl Iterator<Item = Position> + '_ {
+        OFFSETS_8
+            .iter()
+            .filter_map(move |&delta| self.step(pos, delta))
+    }
+
+    /// Maps every cell to a new value, keeping the dimensions.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    fn index(&self, pos: Position) -> usize {
+        pos.1 * self.width + pos.0
+    }
+}
+
+impl<T: PartialEq> Grid<T> {
+    /// Returns the position of the first cell equal to `value` in row-major order.
+    pub fn find(&self, value: &T) -> Option<Position> {
+        self.cells
+            .iter()
+            .position(|cell| cell == value)
+            .map(|i| (i % self.width, i / self.width))
+    }
+
+    /// Iterates the positions of all cells equal to `value` in row-major order.
+    pub fn find_all<'a>(&'a self, value: &'a T) -> impl Iterator<Item = Position> + 'a {
+        self.iter()
+            .filter(move |(_, cell)| *cell == value)
+            .map(|(pos, _)| pos)
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// Creates a grid with every cell set to `value`.
+    pub fn filled(width: usize, height: usize, value: T) -> Self {
+        Self {
+            cells: vec![value; width * height],
+            width,
+            height,
+        }
+    }
+}
+
+impl Grid<char> {
+    /// Parses a grid of characters.
+    pub fn parse_chars(input: &str) -> Result<Self, ParseGridError> {
+        Self::parse(input, |c| c)
+    }
+}
+
+impl Display for Grid<char> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, row) in self.rows().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            for c in row {
+                write!(f, "{c}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T> std::ops::Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Position) -> &Self::Output {
+        self.get(pos).expect("position out of grid bounds")
+    }
+}
+
+impl<T> std::ops::IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, pos: Position) -> &mut Self::Output {
+        self.get_mut(pos).expect("position out of grid bounds")
+    }
+}
+
+impl<T> std::ops::Index<Point<usize>> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Point<usize>) -> &Self::Output {
+        &self[(pos.x, pos.y)]
+    }
+}
+
+impl<T> std::ops::IndexMut<Point<usize>> for Grid<T> {
+    fn index_mut(&mut self, pos: Point<usize>) -> &mut Self::Output {
+        &mut self[(pos.x, pos.y)]
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An error which can be returned when parsing a [`Grid`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseGridError {
+    Empty,
+    RaggedLine {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl Error for ParseGridError {}
+
+impl Display for ParseGridError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseGridError::Empty => f.write_str("expecting at least one non-empty line"),
+            ParseGridError::RaggedLine {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "line {line} has {found} cells, expecting {expected} like the first line"
+            ),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Grid, ParseGridError};
+
+    fn get_mock_grid() -> Grid<char> {
+        Grid::parse_chars("abc\ndef\nghi\n").unwrap()
+    }
+
+    #[test]
+    fn parses_dimensions() {
+        let grid = get_mock_grid();
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 3);
+        assert_eq!(grid.get((2, 1)), Some(&'f'));
+        assert_eq!(grid.get((3, 1)), None);
+    }
+
+    #[test]
+    fn rejects_ragged_lines() {
+        let res = Grid::parse_chars("abc\nde\n");
+        assert_eq!(
+            res,
+            Err(ParseGridError::RaggedLine {
+                line: 2,
+                expected: 3,
+                found: 2
+            })
+        );
+        assert_eq!(Grid::parse_chars(""), Err(ParseGridError::Empty));
+    }
+
+    #[test]
+    fn iterates_rows_columns_and_diagonals() {
+        let grid = get_mock_grid();
+        assert_eq!(grid.row(1).collect::<String>(), "def");
+        assert_eq!(grid.column(2).collect::<String>(), "cfi");
+        assert_eq!(grid.diagonal((0, 0)).collect::<String>(), "aei");
+        assert_eq!(grid.anti_diagonal((2, 0)).collect::<String>(), "ceg");
+        assert_eq!(grid.row(3).count(), 0);
+    }
+
+    #[test]
+    fn iterates_rows_of_empty_grids() {
+        assert_eq!(Grid::<char>::new(0, 2, vec![]).unwrap().rows().count(), 0);
+        assert_eq!(Grid::filled(0, 3, '.').rows().count(), 0);
+        assert_eq!(Grid::filled(2, 0, '.').rows().count(), 0);
+    }
+
+    #[test]
+    fn iterates_neighbours() {
+        let grid = get_mock_grid();
+        assert_eq!(
+            grid.neighbours_4((0, 0)).collect::<Vec<_>>(),
+            [(1, 0), (0, 1)]
+        );
+        assert_eq!(grid.neighbours_8((1, 1)).count(), 8);
+        assert_eq!(grid.neighbours_8((2, 2)).count(), 3);
+    }
+
+    #[test]
+    fn finds_values() {
+        let grid = Grid::parse_chars("#.#\n.#.\n").unwrap();
+        assert_eq!(grid.find(&'.'), Some((1, 0)));
+        assert_eq!(
+            grid.find_all(&'#').collect::<Vec<_>>(),
+            [(0, 0), (2, 0), (1, 1)]
+        );
+        assert_eq!(grid.find(&'x'), None);
+    }
+
+    #[test]
+    fn displays_chars() {
+        let mut grid = get_mock_grid();
+        grid[(1, 1)] = '#';
+        assert_eq!(grid.to_string(), "abc\nd#f\nghi");
+    }
+}
@@ -1,4 +1,6 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, download, examples, profile, read, scaffold, solve, sync_tests, time, verify,
+};
 use args::{parse, AppArguments};
 
 #[cfg(feature = "today")]
@@ -7,73 +9,262 @@ use advent_of_code::template::Day;
 use std::process;
 
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+    use advent_of_code::template::{
+        bench_config::{parse_duration, BenchConfig, DayBudget},
+        Day, InputSource, Year,
+    };
+    use std::{path::PathBuf, process};
+
+    /// Slowdown in percent that `time --compare` tolerates before failing.
+    const DEFAULT_REGRESSION_THRESHOLD: f64 = 10.0;
 
     pub enum AppArguments {
         Download {
             day: Day,
+            examples: bool,
+        },
+        Examples {
+            day: Day,
+            list: bool,
+            picks: Vec<usize>,
+            overwrite: bool,
         },
         Read {
             day: Day,
         },
+        SyncTests {
+            day: Day,
+        },
         Scaffold {
             day: Day,
             download: bool,
             overwrite: bool,
+            template: Option<String>,
+            answer_type: Option<String>,
         },
         Solve {
             day: Day,
             release: bool,
             dhat: bool,
+            /// Record a CPU profile with `perf` instead of running normally.
+            cpu_profile: bool,
             submit: Option<u8>,
+            time: bool,
+            bench_config: BenchConfig,
+            input: InputSource,
+            inputs_dir: Option<PathBuf>,
         },
         All {
             release: bool,
+            time: bool,
+            bench_config: BenchConfig,
+            in_process: bool,
+            jobs: usize,
         },
         Time {
             all: bool,
             day: Option<Day>,
             store: bool,
+            stats: bool,
+            bench_config: BenchConfig,
+            compare: Option<f64>,
+        },
+        History {
+            day: Day,
+        },
+        Verify {
+            day: Option<Day>,
+            release: bool,
+            record: bool,
+            inputs_dir: Option<PathBuf>,
         },
         #[cfg(feature = "today")]
         Today,
     }
 
-    pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
-        let mut args = pico_args::Arguments::from_env();
+    /// Parse `--profile cpu` or `--profile heap` of `solve`, the latter being the same as `--dhat`.
+    /// Returns whether a CPU and a heap profile were requested.
+    fn parse_profile(
+        args: &mut pico_args::Arguments,
+    ) -> Result<(bool, bool), Box<dyn std::error::Error>> {
+        let profile: Option<String> = args.opt_value_from_str("--profile")?;
+        match profile.as_deref() {
+            None => Ok((false, false)),
+            Some("cpu") => Ok((true, false)),
+            Some("heap") => Ok((false, true)),
+            Some(x) => Err(format!("unknown profile `{x}`, expected `cpu` or `heap`").into()),
+        }
+    }
 
-        let app_args = match args.subcommand()?.as_deref() {
-            Some("all") => AppArguments::All {
-                release: args.contains("--release"),
+    /// Parse the input options of `solve`: `--input <path>`, `--input -` or `-` for stdin, and `--example [n]`.
+    /// Must be called after all other options, as the number of the example is a free argument.
+    fn parse_input_source(
+        args: &mut pico_args::Arguments,
+    ) -> Result<InputSource, Box<dyn std::error::Error>> {
+        let input: Option<String> = args.opt_value_from_str("--input")?;
+        let is_example = args.contains("--example");
+        let is_stdin = args.contains("-");
+
+        match input {
+            Some(_) if is_example => Err("use either --input or --example, not both".into()),
+            Some(path) if path == "-" => Ok(InputSource::Stdin),
+            Some(path) => Ok(InputSource::File(path.into())),
+            None if is_example => match args.opt_free_from_str()?.unwrap_or(1) {
+                0 => Err("examples are numbered from 1".into()),
+                n => Ok(InputSource::Example(n)),
             },
+            None if is_stdin => Ok(InputSource::Stdin),
+            None => Ok(InputSource::Puzzle),
+        }
+    }
+
+    /// Parse the bench budget options shared by `solve`, `all` and `time`, on top of `AOC_BENCH_*` env variables.
+    fn parse_bench_config(
+        args: &mut pico_args::Arguments,
+    ) -> Result<BenchConfig, Box<dyn std::error::Error>> {
+        let mut config = BenchConfig::from_env()?;
+
+        if let Some(budget) = args.opt_value_from_fn("--budget", parse_duration)? {
+            config.budget = budget;
+        }
+        if let Some(min_samples) = args.opt_value_from_str("--min-samples")? {
+            config.min_samples = min_samples;
+        }
+        if let Some(max_samples) = args.opt_value_from_str("--max-samples")? {
+            config.max_samples = max_samples;
+        }
+        if args.contains("--allocs") {
+            config.count_allocations = true;
+        }
+        config
+            .day_budgets
+            .extend(args.values_from_str::<_, DayBudget>("--day-budget")?);
+
+        Ok(config.validate()?)
+    }
+
+    /// Parse the command and the `--year` it applies to, [`None`] for the default year.
+    pub fn parse() -> Result<(AppArguments, Option<Year>), Box<dyn std::error::Error>> {
+        let mut args = pico_args::Arguments::from_env();
+
+        let subcommand = args.subcommand()?;
+        let year = args.opt_value_from_str("--year")?;
+
+        let app_args = match subcommand.as_deref() {
+            Some("all") => {
+                let time = args.contains("--time");
+                let in_process = args.contains("--in-process");
+                if time && in_process {
+                    return Err(
+                        "--in-process does not keep timings, use `cargo time` or drop --in-process"
+                            .into(),
+                    );
+                }
+                AppArguments::All {
+                    release: args.contains("--release"),
+                    time,
+                    bench_config: parse_bench_config(&mut args)?,
+                    in_process,
+                    jobs: args.opt_value_from_str("--jobs")?.unwrap_or(1),
+                }
+            }
             Some("time") => {
+                if let Some(day) = args.opt_value_from_str("--history")? {
+                    return finish(args, AppArguments::History { day }, year);
+                }
+
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let stats = args.contains("--stats");
+                let bench_config = parse_bench_config(&mut args)?;
+                let compare = args.contains("--compare");
+                let threshold = args
+                    .opt_value_from_str("--threshold")?
+                    .unwrap_or(DEFAULT_REGRESSION_THRESHOLD);
 
                 AppArguments::Time {
                     all,
                     day: args.opt_free_from_str()?,
                     store,
+                    stats,
+                    bench_config,
+                    compare: compare.then_some(threshold),
                 }
             }
+            Some("verify") => AppArguments::Verify {
+                release: args.contains("--release"),
+                record: args.contains("--record"),
+                inputs_dir: args.opt_value_from_str("--inputs-dir")?,
+                day: args.opt_free_from_str()?,
+            },
             Some("download") => AppArguments::Download {
+                examples: args.contains("--examples"),
+                day: args.free_from_str()?,
+            },
+            Some("examples") => AppArguments::Examples {
+                list: args.contains("--list"),
+                picks: args.values_from_str("--pick")?,
+                overwrite: args.contains("--overwrite"),
                 day: args.free_from_str()?,
             },
             Some("read") => AppArguments::Read {
                 day: args.free_from_str()?,
             },
+            Some("sync-tests") => AppArguments::SyncTests {
+                day: args.free_from_str()?,
+            },
             Some("scaffold") => AppArguments::Scaffold {
                 day: args.free_from_str()?,
                 download: args.contains("--download"),
                 overwrite: args.contains("--overwrite"),
+                template: args.opt_value_from_str("--template")?,
+                answer_type: args.opt_value_from_str("--answer-type")?,
             },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
-            },
+            Some("solve") => {
+                let (cpu_profile, heap_profile) = parse_profile(&mut args)?;
+                let dhat = args.contains("--dhat") || heap_profile;
+                let bench_config = parse_bench_config(&mut args)?;
+                if dhat && bench_config.count_allocations {
+                    return Err("use either --dhat or --allocs, not both".into());
+                }
+                let day = args.free_from_str()?;
+                let release = args.contains("--release");
+                let submit = args.opt_value_from_str("--submit")?;
+                let time = args.contains("--time");
+                let inputs_dir = args.opt_value_from_str("--inputs-dir")?;
+                // the profile is recorded from an optimized `profiling` build that runs every part once.
+                if cpu_profile
+                    && (release
+                        || dhat
+                        || time
+                        || submit.is_some()
+                        || bench_config.count_allocations
+                        || inputs_dir.is_some())
+                {
+                    return Err("--profile cpu can't be combined with --release, --dhat, --time, --submit, --allocs or --inputs-dir".into());
+                }
+                let input = parse_input_source(&mut args)?;
+                // every file of the directory is its own input, which is never submitted.
+                if inputs_dir.is_some()
+                    && (submit.is_some()
+                        || dhat
+                        || bench_config.count_allocations
+                        || !matches!(input, InputSource::Puzzle))
+                {
+                    return Err("--inputs-dir can't be combined with --submit, --dhat, --profile heap, --allocs, --input, --example or -".into());
+                }
+                AppArguments::Solve {
+                    day,
+                    release,
+                    submit,
+                    dhat,
+                    cpu_profile,
+                    time,
+                    bench_config,
+                    inputs_dir,
+                    input,
+                }
+            }
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
             Some(x) => {
@@ -86,12 +277,20 @@ mod args {
             }
         };
 
+        finish(args, app_args, year)
+    }
+
+    fn finish(
+        args: pico_args::Arguments,
+        app_args: AppArguments,
+        year: Option<Year>,
+    ) -> Result<(AppArguments, Option<Year>), Box<dyn std::error::Error>> {
         let remaining = args.finish();
         if !remaining.is_empty() {
             eprintln!("Warning: unknown argument(s): {remaining:?}.");
         }
 
-        Ok(app_args)
+        Ok((app_args, year))
     }
 }
 
@@ -101,34 +300,107 @@ fn main() {
             eprintln!("Error: {err}");
             std::process::exit(1);
         }
-        Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
+        Ok((args, year)) => match args {
+            AppArguments::All {
+                release,
+                time,
+                bench_config,
+                in_process,
+                jobs,
+            } => all::handle(year, release, time, &bench_config, in_process, jobs),
+            AppArguments::Time {
+                day,
+                all,
+                store,
+                stats,
+                bench_config,
+                compare,
+            } => time::handle(year, day, all, store, stats, &bench_config, compare),
+            AppArguments::History { day } => time::handle_history(year, day),
+            AppArguments::Verify {
+                day,
+                release,
+                record,
+                inputs_dir,
+            } => match (inputs_dir, day) {
+                (Some(dir), Some(day)) => {
+                    verify::handle_inputs_dir(year, day, &dir, release, record);
+                }
+                (Some(_), None) => {
+                    eprintln!("--inputs-dir needs a day, e.g. `cargo verify 1 --inputs-dir data/inputs/01`.");
+                    std::process::exit(1);
+                }
+                (None, day) => verify::handle(year, day, release, record),
+            },
+            AppArguments::Download { day, examples } => {
+                download::handle(year, day);
+                if examples {
+                    examples::handle(year, day, false, &[], false);
+                }
+            }
+            AppArguments::Examples {
+                day,
+                list,
+                picks,
+                overwrite,
+            } => examples::handle(year, day, list, &picks, overwrite),
+            AppArguments::Read { day } => read::handle(year, day),
             AppArguments::Scaffold {
                 day,
                 download,
                 overwrite,
+                template,
+                answer_type,
             } => {
-                scaffold::handle(day, overwrite);
+                scaffold::handle(
+                    year,
+                    day,
+                    overwrite,
+                    template.as_deref(),
+                    answer_type.as_deref(),
+                );
                 if download {
-                    download::handle(day);
+                    download::handle(year, day);
+                    examples::handle(year, day, false, &[], false);
+                    sync_tests::handle(year, day);
                 }
             }
+            AppArguments::SyncTests { day } => sync_tests::handle(year, day),
             AppArguments::Solve {
                 day,
                 release,
                 dhat,
+                cpu_profile,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                time,
+                bench_config,
+                input,
+                inputs_dir,
+            } => match inputs_dir {
+                _ if cpu_profile => profile::handle(year, day, &input),
+                Some(dir) => {
+                    solve::handle_inputs_dir(year, day, &dir, release, time, &bench_config);
+                }
+                None => solve::handle(
+                    year,
+                    day,
+                    release,
+                    dhat,
+                    bench_config.count_allocations,
+                    submit,
+                    time.then_some(&bench_config),
+                    &input,
+                ),
+            },
             #[cfg(feature = "today")]
             AppArguments::Today => {
                 match Day::today() {
                     Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
+                        scaffold::handle(year, day, false, None, None);
+                        download::handle(year, day);
+                        examples::handle(year, day, false, &[], false);
+                        sync_tests::handle(year, day);
+                        read::handle(year, day)
                     }
                     None => {
                         eprintln!(
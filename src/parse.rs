@@ -0,0 +1,501 @@
+/// Helpers to parse puzzle inputs without regular expressions or `unwrap()`.
+///
+/// The free functions cover the common line formats. [`Cursor`] is a small combinator API for
+/// anything else, e.g. scanning corrupted memory for `mul(x,y)`.
+/// All errors are [`ParseError`]s, which point to the line and column of the problem.
+use std::error::Error;
+use std::fmt::Display;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use crate::grid::{Grid, ParseGridError};
+
+/// All integers of a string, including their sign, e.g. `[-3, 4]` for `"x=-3, y=4"`.
+///
+/// A `-` is a sign unless it follows a digit, so `"1-2"` yields `[1, 2]`.
+/// Numbers that don't fit into `T` are skipped.
+pub fn ints<'a, T: FromStr + 'a>(s: &'a str) -> impl Iterator<Item = T> + 'a {
+    numbers(s, true)
+}
+
+/// All unsigned integers of a string, ignoring any signs, e.g. `[3, 4]` for `"x=-3, y=4"`.
+///
+/// Numbers that don't fit into `T` are skipped.
+pub fn uints<'a, T: FromStr + 'a>(s: &'a str) -> impl Iterator<Item = T> + 'a {
+    numbers(s, false)
+}
+
+fn numbers<'a, T: FromStr + 'a>(s: &'a str, signed: bool) -> impl Iterator<Item = T> + 'a {
+    let bytes = s.as_bytes();
+    let mut i = 0;
+
+    std::iter::from_fn(move || {
+        while i < bytes.len() {
+            if !bytes[i].is_ascii_digit() {
+                i += 1;
+                continue;
+            }
+
+            let mut start = i;
+            if signed
+                && start > 0
+                && bytes[start - 1] == b'-'
+                && (start < 2 || !bytes[start - 2].is_ascii_digit())
+            {
+                start -= 1;
+            }
+
+            while i < bytes.len() && bytes[i].is_ascii_digit() {
+                i += 1;
+            }
+
+            if let Ok(x) = s[start..i].parse() {
+                return Some(x);
+            }
+        }
+        None
+    })
+}
+
+/// The sections of an input that are separated by blank lines, without their trailing line breaks.
+pub fn sections(input: &str) -> impl Iterator<Item = &str> {
+    let line_len = |s: &str| s.find('\n').map_or(s.len(), |i| i + 1);
+    let mut rest = input;
+
+    std::iter::from_fn(move || {
+        // blank lines before the section.
+        loop {
+            let len = line_len(rest);
+            if len == 0 {
+                return None;
+            }
+            if !rest[..len].trim().is_empty() {
+                break;
+            }
+            rest = &rest[len..];
+        }
+
+        let mut end = 0;
+        while end < rest.len() {
+            let len = line_len(&rest[end..]);
+            if rest[end..end + len].trim().is_empty() {
+                break;
+            }
+            end += len;
+        }
+
+        let (section, tail) = rest.split_at(end);
+        rest = tail;
+        Some(section.trim_end_matches(['\r', '\n']))
+    })
+}
+
+/// Parses every line of an input with `f`, setting the line number of its errors.
+pub fn lines<'a, T: 'a>(
+    input: &'a str,
+    mut f: impl FnMut(&'a str) -> Result<T, ParseError> + 'a,
+) -> impl Iterator<Item = Result<T, ParseError>> + 'a {
+    input
+        .lines()
+        .enumerate()
+        .map(move |(i, line)| f(line).map_err(|e| e.at_line(i + 1)))
+}
+
+/// Parses a grid of characters, see [`Grid::parse_chars`].
+pub fn char_grid(input: &str) -> Result<Grid<char>, ParseError> {
+    Grid::parse_chars(input).map_err(|e| match e {
+        ParseGridError::Empty => ParseError::new(1, 1, e.to_string()),
+        ParseGridError::RaggedLine {
+            line,
+            expected,
+            found,
+        } => ParseError::new(line, expected.min(found) + 1, e.to_string()),
+    })
+}
+
+/// Parses a pair of values around a separator, e.g. `47|53` with separator `|`.
+/// Whitespace around the values is ignored.
+pub fn pair<A: FromStr, B: FromStr>(s: &str, separator: &str) -> Result<(A, B), ParseError> {
+    let Some((a, b)) = s.split_once(separator) else {
+        return Err(ParseError::new(
+            1,
+            s.chars().count() + 1,
+            format!("expected `{separator}`, found end of line"),
+        ));
+    };
+    Ok((value(s, a)?, value(s, b)?))
+}
+
+/// Parses a line like `190: 10 19`, with a key before the colon and values separated by spaces or commas.
+///
+/// The values are parsed lazily, collect them with `.collect::<Result<Vec<_>, _>>()?`.
+pub fn key_values<'a, K: FromStr, V: FromStr + 'a>(
+    s: &'a str,
+) -> Result<(K, impl Iterator<Item = Result<V, ParseError>> + 'a), ParseError> {
+    let Some((key, values)) = s.split_once(':') else {
+        return Err(ParseError::new(
+            1,
+            s.chars().count() + 1,
+            "expected `:`, found end of line",
+        ));
+    };
+
+    let values = values
+        .split([' ', ','])
+        .filter(|x| !x.trim().is_empty())
+        .map(move |x| value(s, x));
+
+    Ok((value(s, key)?, values))
+}
+
+/// Parses `part` of `line`, pointing errors to its column.
+fn value<T: FromStr>(line: &str, part: &str) -> Result<T, ParseError> {
+    let trimmed = part.trim();
+    trimmed.parse().map_err(|_| {
+        let offset = trimmed.as_ptr() as usize - line.as_ptr() as usize;
+        ParseError::new(
+            1,
+            line[..offset].chars().count() + 1,
+            format!("invalid value `{trimmed}`"),
+        )
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A position in an input that is parsed step by step.
+///
+/// Every step either consumes the input it matched or fails with a [`ParseError`] at the current position.
+/// A cursor is `Copy`, so backtracking is a matter of keeping the previous value, see [`Cursor::attempt`].
+///
+/// ```ignore
+/// let mut c = Cursor::new("mul(2,4)");
+/// c.tag("mul(")?;
+/// let (x, y) = (c.uint::<u32>()?, { c.tag(",")?; c.uint::<u32>()? });
+/// c.tag(")")?;
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cursor<'a> {
+    input: &'a str,
+    offset: usize,
+}
+
+impl<'a> Cursor<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Self { input, offset: 0 }
+    }
+
+    /// The input that has not been consumed yet.
+    pub fn rest(&self) -> &'a str {
+        &self.input[self.offset..]
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rest().is_empty()
+    }
+
+    /// The current line and column, both starting at 1.
+    pub fn position(&self) -> (usize, usize) {
+        let consumed = &self.input[..self.offset];
+        let line_start = consumed.rfind('\n').map_or(0, |i| i + 1);
+        (
+            consumed.matches('\n').count() + 1,
+            consumed[line_start..].chars().count() + 1,
+        )
+    }
+
+    /// An error at the current position, stating what was expected and what was found instead.
+    pub fn error(&self, expected: impl Display) -> ParseError {
+        let (line, column) = self.position();
+        let rest = self.rest();
+        let found = match rest.chars().next() {
+            None => "end of input".to_string(),
+            Some('\n' | '\r') => "end of line".to_string(),
+            Some(_) => {
+                let snippet: String = rest.lines().next().unwrap_or("").chars().take(8).collect();
+                format!("`{snippet}`")
+            }
+        };
+        ParseError::new(line, column, format!("expected {expected}, found {found}"))
+    }
+
+    fn advance(&mut self, len: usize) -> &'a str {
+        let taken = &self.rest()[..len];
+        self.offset += len;
+        taken
+    }
+
+    /// Consumes `tag` exactly.
+    pub fn tag(&mut self, tag: &str) -> Result<&'a str, ParseError> {
+        if self.rest().starts_with(tag) {
+            Ok(self.advance(tag.len()))
+        } else {
+            Err(self.error(format_args!("`{tag}`")))
+        }
+    }
+
+    /// Consumes characters as long as they match `f`, possibly none.
+    pub fn take_while(&mut self, f: impl Fn(char) -> bool) -> &'a str {
+        let len = self.rest().find(|c| !f(c)).unwrap_or(self.rest().len());
+        self.advance(len)
+    }
+
+    /// Skips spaces and tabs, but not line breaks.
+    pub fn skip_spaces(&mut self) {
+        self.take_while(|c| c == ' ' || c == '\t');
+    }
+
+    /// Consumes a number of ASCII digits in `count`, e.g. `1..=3`.
+    pub fn digits(&mut self, count: RangeInclusive<usize>) -> Result<&'a str, ParseError> {
+        let len = self
+            .rest()
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(self.rest().len());
+
+        if !count.contains(&len) {
+            let expected = match (count.start(), count.end()) {
+                (1, &usize::MAX) => "a number".to_string(),
+                (min, max) if min == max => format!("a number of {min} digits"),
+                (min, max) => format!("a number of {min} to {max} digits"),
+            };
+            return Err(self.error(expected));
+        }
+        Ok(self.advance(len))
+    }
+
+    /// Consumes an unsigned integer.
+    pub fn uint<T: FromStr>(&mut self) -> Result<T, ParseError> {
+        let start = *self;
+        self.digits(1..=usize::MAX)?
+            .parse()
+            .map_err(|_| start.error("a number in range"))
+    }
+
+    /// Consumes an integer with an optional `-` sign.
+    pub fn int<T: FromStr>(&mut self) -> Result<T, ParseError> {
+        let start = *self;
+        let mut c = *self;
+        let _ = c.tag("-");
+        c.digits(1..=usize::MAX)
+            .map_err(|_| start.error("a number"))?;
+
+        self.advance(c.offset - self.offset)
+            .parse()
+            .map_err(|_| start.error("a number in range"))
+    }
+
+    /// Consumes a line break, or succeeds at the end of the input.
+    pub fn line_end(&mut self) -> Result<(), ParseError> {
+        if self.is_empty() || self.tag("\n").is_ok() || self.tag("\r\n").is_ok() {
+            Ok(())
+        } else {
+            Err(self.error("end of line"))
+        }
+    }
+
+    /// Succeeds if the whole input has been consumed.
+    pub fn end(&self) -> Result<(), ParseError> {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(self.error("end of input"))
+        }
+    }
+
+    /// Runs `f`, only consuming input if it succeeds.
+    pub fn attempt<T>(&mut self, f: impl FnOnce(&mut Self) -> Result<T, ParseError>) -> Option<T> {
+        let mut c = *self;
+        let x = f(&mut c).ok()?;
+        *self = c;
+        Some(x)
+    }
+
+    /// Runs `f` for as long as it succeeds, possibly never.
+    pub fn many<T>(&mut self, mut f: impl FnMut(&mut Self) -> Result<T, ParseError>) -> Vec<T> {
+        let mut items = vec![];
+        while let Some(x) = self.attempt(&mut f) {
+            items.push(x);
+        }
+        items
+    }
+
+    /// Runs `f` at least once, and again after every `separator`, e.g. for `75,47,61`.
+    pub fn separated<T>(
+        &mut self,
+        separator: &str,
+        mut f: impl FnMut(&mut Self) -> Result<T, ParseError>,
+    ) -> Result<Vec<T>, ParseError> {
+        let mut items = vec![f(self)?];
+        while self.tag(separator).is_ok() {
+            items.push(f(self)?);
+        }
+        Ok(items)
+    }
+
+    /// Every match of `f` in the rest of the input, skipping over anything that does not match.
+    pub fn scan<T>(
+        mut self,
+        mut f: impl FnMut(&mut Self) -> Result<T, ParseError> + 'a,
+    ) -> impl Iterator<Item = T> + 'a
+    where
+        T: 'a,
+    {
+        std::iter::from_fn(move || {
+            while let Some(c) = self.rest().chars().next() {
+                let start = self.offset;
+                match self.attempt(&mut f) {
+                    Some(x) => {
+                        if self.offset == start {
+                            self.advance(c.len_utf8());
+                        }
+                        return Some(x);
+                    }
+                    None => {
+                        self.advance(c.len_utf8());
+                    }
+                }
+            }
+            None
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An error which can be returned when parsing an input, pointing to where it occurred.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// The line of the error, starting at 1.
+    pub line: usize,
+    /// The column of the error in characters, starting at 1.
+    pub column: usize,
+    message: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, message: impl Into<String>) -> Self {
+        Self {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+
+    /// Moves an error of a single line to line `line` of the input.
+    pub fn at_line(self, line: usize) -> Self {
+        Self {
+            line: line + self.line - 1,
+            ..self
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Error for ParseError {}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{char_grid, ints, key_values, lines, pair, sections, uints, Cursor, ParseError};
+
+    #[test]
+    fn extracts_integers() {
+        let line = "p=-3,14 v=2-1 x-5 99999999999";
+        assert_eq!(ints::<i32>(line).collect::<Vec<_>>(), [-3, 14, 2, 1, -5]);
+        assert_eq!(
+            uints::<u64>(line).collect::<Vec<_>>(),
+            [3, 14, 2, 1, 5, 99999999999]
+        );
+        assert_eq!(ints::<u32>("").count(), 0);
+    }
+
+    #[test]
+    fn splits_sections() {
+        let input = "47|53\r\n97|13\r\n\r\n75,47\n  \n\n61\n";
+        assert_eq!(
+            sections(input).collect::<Vec<_>>(),
+            ["47|53\r\n97|13", "75,47", "61"]
+        );
+        assert_eq!(sections("\n\n").count(), 0);
+    }
+
+    #[test]
+    fn parses_line_formats() {
+        assert_eq!(pair::<u32, u32>("47|53", "|"), Ok((47, 53)));
+        assert_eq!(
+            pair::<u32, u32>("47|x3", "|"),
+            Err(ParseError::new(1, 4, "invalid value `x3`"))
+        );
+        assert_eq!(pair::<u32, u32>("47", "|").unwrap_err().column, 3);
+
+        let (key, values) = key_values::<u64, u64>("190: 10 19").unwrap();
+        assert_eq!(key, 190);
+        assert_eq!(values.collect::<Result<Vec<_>, _>>(), Ok(vec![10, 19]));
+    }
+
+    #[test]
+    fn numbers_lines_of_errors() {
+        let res: Result<Vec<(u32, u32)>, _> = lines("1|2\n3|4\n5-6", |l| pair(l, "|")).collect();
+        let err = res.unwrap_err();
+        assert_eq!((err.line, err.column), (3, 4));
+        assert_eq!(
+            err.to_string(),
+            "line 3, column 4: expected `|`, found end of line"
+        );
+
+        let err = char_grid("ab\nabc").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+    }
+
+    #[test]
+    fn parses_with_cursor() {
+        let mut c = Cursor::new("7: -12,4\n3");
+        assert_eq!(c.uint::<u32>(), Ok(7));
+        c.tag(":").unwrap();
+        c.skip_spaces();
+        assert_eq!(c.separated(",", Cursor::int::<i32>), Ok(vec![-12, 4]));
+        c.line_end().unwrap();
+        assert_eq!(c.position(), (2, 1));
+        assert_eq!(
+            c.tag("x").unwrap_err().to_string(),
+            "line 2, column 1: expected `x`, found `3`"
+        );
+        assert_eq!(c.attempt(|c| c.uint::<u8>()), Some(3));
+        assert!(c.end().is_ok());
+    }
+
+    #[test]
+    fn scans_for_matches() {
+        let mul = |c: &mut Cursor| {
+            c.tag("mul(")?;
+            let x: u32 = c.digits(1..=3)?.parse().unwrap();
+            c.tag(",")?;
+            let y: u32 = c.digits(1..=3)?.parse().unwrap();
+            c.tag(")")?;
+            Ok(x * y)
+        };
+        let input = "xmul(2,4)%mul[3,7]!mul(1234,1)mul(11,8)mul ( 2 , 4 )";
+        assert_eq!(Cursor::new(input).scan(mul).collect::<Vec<_>>(), [8, 88]);
+
+        let mut c = Cursor::new("mul(1234,1)");
+        c.tag("mul(").unwrap();
+        assert_eq!(
+            c.digits(1..=3).unwrap_err().message(),
+            "expected a number of 1 to 3 digits, found `1234,1)`"
+        );
+    }
+}
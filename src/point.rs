@@ -0,0 +1,358 @@
+/// Two-dimensional points, vectors and compass directions.
+use std::fmt::Display;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use crate::grid::{OFFSETS_4, OFFSETS_8};
+
+/// An integer type that can be used as the component of a [`Point`].
+pub trait Coord:
+    Copy + Ord + Default + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    /// Widens the value losslessly. All implementors fit into an [`i128`].
+    fn to_i128(self) -> i128;
+
+    /// Narrows the value, returns [`None`] if it does not fit into `Self`.
+    fn from_i128(value: i128) -> Option<Self>;
+}
+
+macro_rules! impl_coord {
+    ($($t:ty),*) => {
+        $(
+            impl Coord for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                #[allow(clippy::cast_lossless, clippy::cast_possible_wrap)]
+                fn to_i128(self) -> i128 {
+                    self as i128
+                }
+
+                fn from_i128(value: i128) -> Option<Self> {
+                    Self::try_from(value).ok()
+                }
+            }
+        )*
+    };
+}
+
+impl_coord!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+/* -------------------------------------------------------------------------- */
+
+/// A point on a two-dimensional integer plane, with `y` growing downwards like in puzzle grids.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Point<T> {
+    pub x: T,
+    pub y: T,
+}
+
+/// The difference between two [`Point`]s.
+pub type Vector<T> = Point<T>;
+
+impl<T: Coord> Point<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+
+    pub fn zero() -> Self {
+        Self::new(T::ZERO, T::ZERO)
+    }
+
+    /// Converts both components into another integer type, returns [`None`] if either does not fit.
+    pub fn cast<U: Coord>(self) -> Option<Point<U>> {
+        Some(Point::new(
+            U::from_i128(self.x.to_i128())?,
+            U::from_i128(self.y.to_i128())?,
+        ))
+    }
+
+    /// Adds `delta` of any integer width, returns [`None`] on overflow.
+    pub fn offset<D: Coord>(self, delta: Vector<D>) -> Option<Self> {
+        Some(Self::new(
+            T::from_i128(self.x.to_i128() + delta.x.to_i128())?,
+            T::from_i128(self.y.to_i128() + delta.y.to_i128())?,
+        ))
+    }
+
+    /// Adds `delta`, returns [`None`] unless the result lies within `(0, 0)..bounds`.
+    ///
+    /// `bounds` is exclusive, e.g. the width and height of a grid.
+    pub fn checked_step<D: Coord>(self, delta: Vector<D>, bounds: Point<T>) -> Option<Self> {
+        let next = self.offset(delta)?;
+        let in_bounds =
+            next.x >= T::ZERO && next.y >= T::ZERO && next.x < bounds.x && next.y < bounds.y;
+        in_bounds.then_some(next)
+    }
+
+    /// Moves one step into `dir`, returns [`None`] unless the result lies within `(0, 0)..bounds`.
+    pub fn step(self, dir: impl Into<Dir8>, bounds: Point<T>) -> Option<Self> {
+        self.checked_step(dir.into().delta(), bounds)
+    }
+
+    /// The taxicab distance `|dx| + |dy|`.
+    ///
+    /// # Panics
+    /// Panics if the distance does not fit into `T`.
+    pub fn manhattan_distance(self, other: Self) -> T {
+        let (dx, dy) = self.abs_diff(other);
+        T::from_i128(dx + dy).expect("manhattan distance to fit into coordinate type")
+    }
+
+    /// The chessboard distance `max(|dx|, |dy|)`.
+    ///
+    /// # Panics
+    /// Panics if the distance does not fit into `T`.
+    pub fn chebyshev_distance(self, other: Self) -> T {
+        let (dx, dy) = self.abs_diff(other);
+        T::from_i128(dx.max(dy)).expect("chebyshev distance to fit into coordinate type")
+    }
+
+    fn abs_diff(self, other: Self) -> (i128, i128) {
+        (
+            (self.x.to_i128() - other.x.to_i128()).abs(),
+            (self.y.to_i128() - other.y.to_i128()).abs(),
+        )
+    }
+}
+
+impl<T: Display> Display for Point<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl<T> From<(T, T)> for Point<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T> From<Point<T>> for (T, T) {
+    fn from(point: Point<T>) -> Self {
+        (point.x, point.y)
+    }
+}
+
+impl<T: Coord> Add for Point<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl<T: Coord> AddAssign for Point<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T: Coord> Sub for Point<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<T: Coord> SubAssign for Point<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<T: Coord> Mul<T> for Point<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        Self::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl<T: Coord + Neg<Output = T>> Neg for Point<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// One of the four orthogonal directions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Dir4 {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Dir4 {
+    /// Iterates all directions clockwise, starting with [`Dir4::Up`].
+    pub fn all() -> impl Iterator<Item = Dir4> {
+        [Dir4::Up, Dir4::Right, Dir4::Down, Dir4::Left].into_iter()
+    }
+
+    pub fn turn_left(self) -> Self {
+        match self {
+            Dir4::Up => Dir4::Left,
+            Dir4::Left => Dir4::Down,
+            Dir4::Down => Dir4::Right,
+            Dir4::Right => Dir4::Up,
+        }
+    }
+
+    pub fn turn_right(self) -> Self {
+        match self {
+            Dir4::Up => Dir4::Right,
+            Dir4::Right => Dir4::Down,
+            Dir4::Down => Dir4::Left,
+            Dir4::Left => Dir4::Up,
+        }
+    }
+
+    pub fn opposite(self) -> Self {
+        match self {
+            Dir4::Up => Dir4::Down,
+            Dir4::Right => Dir4::Left,
+            Dir4::Down => Dir4::Up,
+            Dir4::Left => Dir4::Right,
+        }
+    }
+
+    /// The unit vector pointing into this direction.
+    pub fn delta(self) -> Vector<isize> {
+        let (x, y) = OFFSETS_4[self as usize];
+        Vector::new(x, y)
+    }
+}
+
+/// One of the four orthogonal or four diagonal directions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Dir8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Dir8 {
+    const CLOCKWISE: [Dir8; 8] = [
+        Dir8::Up,
+        Dir8::UpRight,
+        Dir8::Right,
+        Dir8::DownRight,
+        Dir8::Down,
+        Dir8::DownLeft,
+        Dir8::Left,
+        Dir8::UpLeft,
+    ];
+
+    /// Iterates all directions clockwise, starting with [`Dir8::Up`].
+    pub fn all() -> impl Iterator<Item = Dir8> {
+        Self::CLOCKWISE.into_iter()
+    }
+
+    /// Turns 45 degrees counter-clockwise.
+    pub fn turn_left(self) -> Self {
+        Self::CLOCKWISE[(self as usize + 7) % 8]
+    }
+
+    /// Turns 45 degrees clockwise.
+    pub fn turn_right(self) -> Self {
+        Self::CLOCKWISE[(self as usize + 1) % 8]
+    }
+
+    pub fn opposite(self) -> Self {
+        Self::CLOCKWISE[(self as usize + 4) % 8]
+    }
+
+    /// The vector pointing into this direction, with both components in `-1..=1`.
+    pub fn delta(self) -> Vector<isize> {
+        let (x, y) = OFFSETS_8[self as usize];
+        Vector::new(x, y)
+    }
+}
+
+impl From<Dir4> for Dir8 {
+    fn from(dir: Dir4) -> Self {
+        match dir {
+            Dir4::Up => Dir8::Up,
+            Dir4::Right => Dir8::Right,
+            Dir4::Down => Dir8::Down,
+            Dir4::Left => Dir8::Left,
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Dir4, Dir8, Point, Vector};
+
+    #[test]
+    fn does_arithmetic() {
+        let a = Point::new(3_i32, -2);
+        let b = Point::new(1_i32, 4);
+        assert_eq!(a + b, Point::new(4, 2));
+        assert_eq!(a - b, Vector::new(2, -6));
+        assert_eq!(b * 3, Point::new(3, 12));
+        assert_eq!(-a, Point::new(-3, 2));
+    }
+
+    #[test]
+    fn steps_within_bounds() {
+        let bounds = Point::new(3_usize, 2);
+        let origin = Point::new(0_usize, 0);
+        assert_eq!(origin.step(Dir4::Up, bounds), None);
+        assert_eq!(origin.step(Dir4::Left, bounds), None);
+        assert_eq!(origin.step(Dir8::DownRight, bounds), Some(Point::new(1, 1)));
+        assert_eq!(Point::new(2_usize, 1).step(Dir4::Right, bounds), None);
+        assert_eq!(Point::new(2_usize, 1).step(Dir4::Down, bounds), None);
+        assert_eq!(Point::new(u8::MAX, 0).offset(Vector::new(1_i8, 0)), None);
+    }
+
+    #[test]
+    fn measures_distances() {
+        let a = Point::new(1_u32, 5);
+        let b = Point::new(4_u32, 1);
+        assert_eq!(a.manhattan_distance(b), 7);
+        assert_eq!(a.chebyshev_distance(b), 4);
+        assert_eq!(b.manhattan_distance(a), 7);
+    }
+
+    #[test]
+    fn casts_between_widths() {
+        assert_eq!(Point::new(-1_i32, 2).cast::<usize>(), None);
+        assert_eq!(Point::new(1_i32, 2).cast::<usize>(), Some(Point::new(1, 2)));
+    }
+
+    #[test]
+    fn turns_directions() {
+        assert_eq!(Dir4::Up.turn_right(), Dir4::Right);
+        assert_eq!(Dir4::Up.turn_left(), Dir4::Left);
+        assert_eq!(Dir4::Left.opposite(), Dir4::Right);
+        assert_eq!(Dir8::Up.turn_left(), Dir8::UpLeft);
+        assert_eq!(Dir8::UpLeft.turn_right(), Dir8::Up);
+        assert_eq!(Dir8::DownLeft.opposite(), Dir8::UpRight);
+        assert_eq!(Dir4::all().count(), 4);
+        assert_eq!(Dir8::all().count(), 8);
+        assert!(Dir8::all().all(|d| d.delta() == -d.opposite().delta()));
+    }
+
+    #[test]
+    fn points_directions() {
+        assert_eq!(Dir4::Up.delta(), Vector::new(0, -1));
+        assert_eq!(Dir8::DownLeft.delta(), Vector::new(-1, 1));
+        assert!(Dir4::all().all(|d| d.delta() == Dir8::from(d).delta()));
+    }
+}
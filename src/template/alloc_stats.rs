@@ -0,0 +1,220 @@
+/// Counts the heap allocations of solution parts, see `--allocs`.
+///
+/// With the `alloc-stats` feature, solution binaries use [`CountingAlloc`] as their global allocator,
+/// which keeps a few counters on top of the system allocator. Unlike dhat, this needs no external tools
+/// and is cheap enough to leave the timings of a part comparable.
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use tinyjson::JsonValue;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATED_BYTES: AtomicUsize = AtomicUsize::new(0);
+static CURRENT_BYTES: AtomicUsize = AtomicUsize::new(0);
+static PEAK_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+/// A global allocator that counts allocations and tracks the size of the heap.
+/// Reallocations are counted as allocations of their new size.
+pub struct CountingAlloc;
+
+impl CountingAlloc {
+    fn record_alloc(size: usize) {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED_BYTES.fetch_add(size, Ordering::Relaxed);
+        let current = CURRENT_BYTES.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK_BYTES.fetch_max(current, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            Self::record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            Self::record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        CURRENT_BYTES.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            CURRENT_BYTES.fetch_sub(layout.size(), Ordering::Relaxed);
+            Self::record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// Heap allocations of a single execution of a solution part.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    pub allocations: u64,
+    /// Bytes allocated in total, including memory that was freed again.
+    pub bytes: u64,
+    /// Largest amount of memory the part held at once, on top of what was allocated before it ran.
+    pub peak_bytes: u64,
+}
+
+/// Run `func` and count its allocations.
+/// Returns [`None`] for the stats if [`CountingAlloc`] is not the global allocator.
+pub fn measure<T>(func: impl FnOnce() -> T) -> (T, Option<AllocStats>) {
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let bytes = ALLOCATED_BYTES.load(Ordering::Relaxed);
+    let current = CURRENT_BYTES.load(Ordering::Relaxed);
+    PEAK_BYTES.store(current, Ordering::Relaxed);
+
+    let result = func();
+
+    // reading the input allocates, so an installed allocator has always counted something by now.
+    if allocations == 0 {
+        return (result, None);
+    }
+
+    let stats = AllocStats {
+        allocations: (ALLOCATIONS.load(Ordering::Relaxed) - allocations) as u64,
+        bytes: (ALLOCATED_BYTES.load(Ordering::Relaxed) - bytes) as u64,
+        peak_bytes: PEAK_BYTES.load(Ordering::Relaxed).saturating_sub(current) as u64,
+    };
+
+    (result, Some(stats))
+}
+
+/// Formats a byte count with a binary unit, e.g. `1.5 KiB`.
+#[allow(clippy::cast_precision_loss)]
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = UNITS[0];
+    for next in &UNITS[1..] {
+        if value < 1024.0 {
+            break;
+        }
+        value /= 1024.0;
+        unit = next;
+    }
+    format!("{value:.1} {unit}")
+}
+
+impl Display for AllocStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let allocations = self.allocations;
+        let noun = if allocations == 1 {
+            "allocation"
+        } else {
+            "allocations"
+        };
+        write!(
+            f,
+            "{allocations} {noun}, {}, peak {}",
+            format_bytes(self.bytes),
+            format_bytes(self.peak_bytes)
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&AllocStats> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &AllocStats) -> Self {
+        let map: HashMap<String, JsonValue> = [
+            ("allocations", value.allocations),
+            ("bytes", value.bytes),
+            ("peak_bytes", value.peak_bytes),
+        ]
+        .into_iter()
+        .map(|(key, x)| (key.to_string(), JsonValue::Number(x as f64)))
+        .collect();
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for AllocStats {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected allocation stats to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .map(|v| v as u64)
+                .ok_or(format!("Expected allocations.{key} to be a number."))
+        };
+
+        Ok(AllocStats {
+            allocations: number("allocations")?,
+            bytes: number("bytes")?,
+            peak_bytes: number("peak_bytes")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::{format_bytes, measure, AllocStats};
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+
+    #[test]
+    fn formats_stats() {
+        let stats = AllocStats {
+            allocations: 12,
+            bytes: 4096,
+            peak_bytes: 800,
+        };
+        assert_eq!(stats.to_string(), "12 allocations, 4.0 KiB, peak 800 B");
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let stats = AllocStats {
+            allocations: 1,
+            bytes: 2,
+            peak_bytes: 3,
+        };
+        assert_eq!(AllocStats::try_from(&JsonValue::from(&stats)), Ok(stats));
+    }
+
+    #[test]
+    fn skips_default_allocator() {
+        // the test binary uses the system allocator, so nothing is counted.
+        let (result, stats) = measure(|| vec![1, 2, 3].len());
+        assert_eq!(result, 3);
+        assert_eq!(stats, None);
+    }
+}
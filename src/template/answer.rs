@@ -0,0 +1,229 @@
+use std::fmt::Display;
+
+/// A value that a solution part can return.
+///
+/// Implemented for integers, including big ones like [`u128`], for strings and for [`AsciiArt`].
+/// Implement it for your own types to control how their answer is printed and submitted.
+pub trait Answer {
+    /// The answer as printed to the terminal, which may span multiple lines.
+    fn display(&self) -> String;
+
+    /// The answer as submitted to the website and stored in `data/answers`.
+    /// [`None`] if the answer can't be submitted as is.
+    fn submission(&self) -> Option<String> {
+        Some(self.display())
+    }
+}
+
+macro_rules! impl_answer {
+    ($($t:ty),*) => {
+        $(
+            impl Answer for $t {
+                fn display(&self) -> String {
+                    self.to_string()
+                }
+            }
+        )*
+    };
+}
+
+impl_answer!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, char, String, &str);
+
+/* -------------------------------------------------------------------------- */
+
+/// The return type of a solution part: [`Option`] or [`Result`] of an [`Answer`].
+///
+/// [`None`] marks a part as not implemented yet, an [`Err`] marks it as failed.
+pub trait PartOutput {
+    type Answer: Answer;
+
+    /// The answer of the part, [`None`] if it is not implemented, or the message of its error.
+    fn into_result(self) -> Result<Option<Self::Answer>, String>;
+}
+
+impl<T: Answer> PartOutput for Option<T> {
+    type Answer = T;
+
+    fn into_result(self) -> Result<Option<T>, String> {
+        Ok(self)
+    }
+}
+
+impl<T: Answer, E: Display> PartOutput for Result<T, E> {
+    type Answer = T;
+
+    fn into_result(self) -> Result<Option<T>, String> {
+        self.map(Some).map_err(|e| e.to_string())
+    }
+}
+
+impl<T: Answer, E: Display> PartOutput for Result<Option<T>, E> {
+    type Answer = T;
+
+    fn into_result(self) -> Result<Option<T>, String> {
+        self.map_err(|e| e.to_string())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Letters drawn with pixels, as some puzzles ask to read them off a rendered grid.
+///
+/// Printed as an image, submitted as the letters it spells, e.g. `EHZRUBLU`.
+/// Letters are recognized in the 4x6 font the puzzles use.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AsciiArt {
+    pixels: Vec<Vec<bool>>,
+}
+
+impl AsciiArt {
+    /// Creates [`AsciiArt`] from rows of pixels, `true` being lit.
+    pub fn from_pixels<R: IntoIterator<Item = bool>>(rows: impl IntoIterator<Item = R>) -> Self {
+        Self {
+            pixels: rows
+                .into_iter()
+                .map(|row| row.into_iter().collect())
+                .collect(),
+        }
+    }
+
+    /// Creates [`AsciiArt`] from text, where `#` and `█` are lit pixels, e.g. `.##.\n#..#`. Blank lines are skipped.
+    pub fn from_text(text: &str) -> Self {
+        Self::from_pixels(
+            text.lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(|line| line.chars().map(|c| c == '#' || c == '█')),
+        )
+    }
+
+    /// The letters spelled by the pixels, [`None`] if any of them is not recognized.
+    pub fn letters(&self) -> Option<String> {
+        if self.pixels.len() != LETTER_HEIGHT {
+            return None;
+        }
+
+        let width = self.pixels.iter().map(Vec::len).max()?;
+        let is_lit = |x: usize, y: usize| self.pixels[y].get(x).copied().unwrap_or(false);
+        let is_blank_column = |x: usize| (0..LETTER_HEIGHT).all(|y| !is_lit(x, y));
+
+        let mut letters = String::new();
+        let mut x = 0;
+
+        while x < width {
+            if is_blank_column(x) {
+                x += 1;
+                continue;
+            }
+
+            let start = x;
+            while x < width && !is_blank_column(x) {
+                x += 1;
+            }
+
+            let glyph: Vec<String> = (0..LETTER_HEIGHT)
+                .map(|y| {
+                    (start..x)
+                        .map(|x| if is_lit(x, y) { '#' } else { '.' })
+                        .collect()
+                })
+                .collect();
+
+            let (letter, _) = LETTERS.iter().find(|(_, rows)| *rows == glyph.as_slice())?;
+            letters.push(*letter);
+        }
+
+        (!letters.is_empty()).then_some(letters)
+    }
+}
+
+impl Display for AsciiArt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let rows: Vec<String> = self
+            .pixels
+            .iter()
+            .map(|row| row.iter().map(|x| if *x { '█' } else { ' ' }).collect())
+            .collect();
+        write!(f, "{}", rows.join("\n"))
+    }
+}
+
+impl Answer for AsciiArt {
+    fn display(&self) -> String {
+        self.to_string()
+    }
+
+    fn submission(&self) -> Option<String> {
+        self.letters()
+    }
+}
+
+const LETTER_HEIGHT: usize = 6;
+
+#[rustfmt::skip]
+const LETTERS: [(char, [&str; LETTER_HEIGHT]); 18] = [
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', ["###", ".#.", ".#.", ".#.", ".#.", "###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Y', ["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Answer, AsciiArt, PartOutput};
+
+    #[test]
+    fn displays_primitives() {
+        assert_eq!(42u32.display(), "42");
+        assert_eq!(u128::MAX.submission(), Some(u128::MAX.to_string()));
+        assert_eq!("1,2,3".to_string().submission().as_deref(), Some("1,2,3"));
+    }
+
+    #[test]
+    fn reads_letters() {
+        let art = AsciiArt::from_text(
+            "
+####.#..#.###..#..#.
+#....#..#.#..#.#..#.
+###..####.#..#.#..#.
+#....#..#.###..#..#.
+#....#..#.#.#..#..#.
+####.#..#.#..#..##..",
+        );
+        assert_eq!(art.submission().as_deref(), Some("EHRU"));
+        assert!(art.display().starts_with("████ █  █ ███"));
+    }
+
+    #[test]
+    fn rejects_unknown_letters() {
+        assert_eq!(AsciiArt::from_text("#\n#\n#").letters(), None);
+        let art = AsciiArt::from_pixels(vec![vec![true, true]; 6]);
+        assert_eq!(art.submission(), None);
+    }
+
+    #[test]
+    fn converts_part_outputs() {
+        assert_eq!(Some(1u32).into_result(), Ok(Some(1)));
+        assert_eq!(None::<u32>.into_result(), Ok(None));
+        assert_eq!(
+            "x".parse::<u32>().into_result(),
+            Err("invalid digit found in string".to_string())
+        );
+        assert_eq!(Ok::<_, String>(None::<u32>).into_result(), Ok(None));
+    }
+}
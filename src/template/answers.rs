@@ -0,0 +1,80 @@
+/// Known-correct answers for real puzzle inputs, stored as one file per part in `data/answers`, e.g. `01-2.txt`.
+use std::{env, fs, io, path::PathBuf};
+
+use crate::template::{data_dir, Day, Year};
+
+fn get_path(year: Option<Year>, day: Day, part: u8) -> PathBuf {
+    env::current_dir()
+        .unwrap()
+        .join(data_dir(year))
+        .join("answers")
+        .join(format!("{day}-{part}.txt"))
+}
+
+/// Read the stored answer of a part, [`None`] if there is none yet.
+pub fn read(year: Option<Year>, day: Day, part: u8) -> Option<String> {
+    let answer = fs::read_to_string(get_path(year, day, part)).ok()?;
+    let answer = answer.trim_end_matches(['\r', '\n']);
+    (!answer.is_empty()).then(|| answer.to_string())
+}
+
+/// Store `answer` as the known-correct answer of a part, replacing any previous one.
+pub fn store(year: Option<Year>, day: Day, part: u8, answer: &str) -> Result<(), io::Error> {
+    let path = get_path(year, day, part);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, format!("{answer}\n"))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Outcome of checking an answer against the stored one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    /// The part returned a different answer, or none at all.
+    Fail {
+        expected: String,
+    },
+    /// No answer has been stored for the part yet.
+    Missing,
+}
+
+pub fn check(expected: Option<&str>, actual: Option<&str>) -> Verdict {
+    match expected {
+        None => Verdict::Missing,
+        Some(expected) if actual.map(|x| x.trim_end_matches(['\r', '\n'])) == Some(expected) => {
+            Verdict::Pass
+        }
+        Some(expected) => Verdict::Fail {
+            expected: expected.to_string(),
+        },
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{check, Verdict};
+
+    #[test]
+    fn checks_answers() {
+        assert_eq!(check(Some("42"), Some("42")), Verdict::Pass);
+        assert_eq!(check(Some("a\nb"), Some("a\nb\n")), Verdict::Pass);
+        assert_eq!(
+            check(Some("42"), Some("43")),
+            Verdict::Fail {
+                expected: "42".into()
+            }
+        );
+        assert_eq!(
+            check(Some("42"), None),
+            Verdict::Fail {
+                expected: "42".into()
+            }
+        );
+        assert_eq!(check(None, Some("42")), Verdict::Missing);
+    }
+}
@@ -1,25 +1,107 @@
-/// Wrapper module around the "aoc-cli" command-line.
+/// Wrapper module around the "aoc-cli" command-line, an optional backend of [`crate::template::aoc_client`].
 use std::{
+    env,
     fmt::Display,
+    fs,
+    path::PathBuf,
     process::{Command, Output, Stdio},
 };
 
-use crate::template::Day;
+use crate::template::aoc_client::{AocClient, AocClientError};
+use crate::template::submissions::{self, Outcome};
+use crate::template::{Day, Year};
 
 #[derive(Debug)]
 pub enum AocCommandError {
     CommandNotFound,
     CommandNotCallable,
+    /// The session cookie is missing or expired.
+    NotLoggedIn,
+    /// The puzzle is not unlocked yet.
+    PuzzleLocked,
+    /// The part is already solved, or part one has to be solved first.
+    AlreadySolved,
+    /// The answer was checked and is not correct. Holds the response of the website.
+    WrongAnswer(String),
+    /// The answer was not checked because the previous submission was too recent.
+    RateLimited {
+        wait_seconds: Option<u64>,
+    },
     BadExitStatus(Output),
 }
 
+impl AocCommandError {
+    /// The verdict on a submitted answer, if the error is one.
+    pub fn outcome(&self) -> Option<Outcome> {
+        match self {
+            AocCommandError::WrongAnswer(message) => Some(Outcome::from_message(message)),
+            AocCommandError::RateLimited { wait_seconds } => Some(Outcome::RateLimited {
+                wait_seconds: *wait_seconds,
+            }),
+            AocCommandError::AlreadySolved => Some(Outcome::WrongLevel),
+            _ => None,
+        }
+    }
+
+    /// Recognize known responses in the output of aoc-cli.
+    fn parse(output: &str) -> Option<Self> {
+        let lowercase = output.to_lowercase();
+
+        if lowercase.contains("session cookie")
+            || lowercase.contains("not logged in")
+            || lowercase.contains("/auth/login")
+        {
+            Some(AocCommandError::NotLoggedIn)
+        } else if lowercase.contains("not unlocked")
+            || lowercase.contains("not available yet")
+            || lowercase.contains("404 not found")
+        {
+            Some(AocCommandError::PuzzleLocked)
+        } else if lowercase.contains("solving the right level") {
+            Some(AocCommandError::AlreadySolved)
+        } else if lowercase.contains("answer too recently") {
+            Some(AocCommandError::RateLimited {
+                wait_seconds: submissions::parse_wait(output),
+            })
+        } else if lowercase.contains("not the right answer") {
+            Some(AocCommandError::WrongAnswer(output.trim().to_string()))
+        } else {
+            None
+        }
+    }
+}
+
 impl Display for AocCommandError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             AocCommandError::CommandNotFound => write!(f, "aoc-cli is not present in environment."),
             AocCommandError::CommandNotCallable => write!(f, "aoc-cli could not be called."),
-            AocCommandError::BadExitStatus(_) => {
-                write!(f, "aoc-cli exited with a non-zero status.")
+            AocCommandError::NotLoggedIn => write!(
+                f,
+                "aoc-cli is not logged in. Refresh the session cookie in ~/.adventofcode.session."
+            ),
+            AocCommandError::PuzzleLocked => write!(f, "the puzzle is not unlocked yet."),
+            AocCommandError::AlreadySolved => write!(
+                f,
+                "the part is already solved, or the previous part is not solved yet."
+            ),
+            AocCommandError::WrongAnswer(message) => write!(f, "{message}"),
+            AocCommandError::RateLimited {
+                wait_seconds: Some(secs),
+            } => write!(
+                f,
+                "submitted too recently, wait {secs}s before trying again."
+            ),
+            AocCommandError::RateLimited { wait_seconds: None } => {
+                write!(f, "submitted too recently, wait before trying again.")
+            }
+            AocCommandError::BadExitStatus(output) => {
+                write!(f, "aoc-cli exited with {}.", output.status)?;
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                match stderr.trim().lines().last() {
+                    Some(line) => write!(f, " Last output: {line}"),
+                    None => Ok(()),
+                }
             }
         }
     }
@@ -33,93 +115,129 @@ pub fn check() -> Result<(), AocCommandError> {
     Ok(())
 }
 
-pub fn read(day: Day) -> Result<Output, AocCommandError> {
-    let puzzle_path = get_puzzle_path(day);
+/// Sends requests by calling aoc-cli, which reads the session cookie on its own.
+pub struct AocCli;
 
-    let args = build_args(
-        "read",
-        &[
-            "--description-only".into(),
-            "--puzzle-file".into(),
-            puzzle_path,
-        ],
-        day,
-    );
+impl AocClient for AocCli {
+    fn input(&self, year: Year, day: Day) -> Result<String, AocClientError> {
+        download_to_temp_file("--input-only", "--input-file", year, day)
+    }
+
+    fn puzzle(&self, year: Year, day: Day) -> Result<String, AocClientError> {
+        download_to_temp_file("--puzzle-only", "--puzzle-file", year, day)
+    }
 
-    call_aoc_cli(&args)
+    fn submit(
+        &self,
+        year: Year,
+        day: Day,
+        part: u8,
+        answer: &str,
+    ) -> Result<String, AocClientError> {
+        // workaround: the argument order is inverted for submit.
+        let mut args = build_args("submit", &[], year, day);
+        args.push(part.to_string());
+        args.push(answer.to_string());
+
+        let output = call_aoc_cli(&args)?;
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
 }
 
-pub fn download(day: Day) -> Result<Output, AocCommandError> {
-    let input_path = get_input_path(day);
-    let puzzle_path = get_puzzle_path(day);
+/// aoc-cli writes downloads to files, so download to a temporary one and read it back.
+fn download_to_temp_file(
+    only_flag: &str,
+    file_flag: &str,
+    year: Year,
+    day: Day,
+) -> Result<String, AocClientError> {
+    let path: PathBuf = env::temp_dir().join(format!("aoc-{year}-{day}-{}", std::process::id()));
 
     let args = build_args(
         "download",
         &[
             "--overwrite".into(),
-            "--input-file".into(),
-            input_path.to_string(),
-            "--puzzle-file".into(),
-            puzzle_path.to_string(),
+            only_flag.into(),
+            file_flag.into(),
+            path.display().to_string(),
         ],
+        year,
         day,
     );
 
     let output = call_aoc_cli(&args)?;
-    println!("---");
-    println!("🎄 Successfully wrote input to \"{}\".", &input_path);
-    println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
-    Ok(output)
-}
-
-pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandError> {
-    // workaround: the argument order is inverted for submit.
-    let mut args = build_args("submit", &[], day);
-    args.push(part.to_string());
-    args.push(result.to_string());
-    call_aoc_cli(&args)
-}
-
-fn get_input_path(day: Day) -> String {
-    format!("data/inputs/{day}.txt")
+    print!("{}", String::from_utf8_lossy(&output.stdout));
+    let content = fs::read_to_string(&path)?;
+    fs::remove_file(&path)?;
+    Ok(content)
 }
 
-fn get_puzzle_path(day: Day) -> String {
-    format!("data/puzzles/{day}.md")
-}
-
-fn get_year() -> Option<u16> {
-    match std::env::var("AOC_YEAR") {
-        Ok(x) => x.parse().ok().or(None),
-        Err(_) => None,
-    }
-}
-
-fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
+fn build_args(command: &str, args: &[String], year: Year, day: Day) -> Vec<String> {
     let mut cmd_args = args.to_vec();
 
-    if let Some(year) = get_year() {
-        cmd_args.push("--year".into());
-        cmd_args.push(year.to_string());
-    }
+    cmd_args.push("--year".into());
+    cmd_args.push(year.to_string());
 
     cmd_args.append(&mut vec!["--day".into(), day.to_string(), command.into()]);
 
     cmd_args
 }
 
+/// Call aoc-cli, capturing its output to recognize known responses. Its log output is echoed to stderr.
 fn call_aoc_cli(args: &[String]) -> Result<Output, AocCommandError> {
     // println!("Calling >aoc with: {}", args.join(" "));
     let output = Command::new("aoc")
         .args(args)
-        .stdout(Stdio::inherit())
-        .stderr(Stdio::inherit())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
         .output()
         .map_err(|_| AocCommandError::CommandNotCallable)?;
 
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    eprint!("{stderr}");
+
+    if let Some(e) = AocCommandError::parse(&format!("{stdout}\n{stderr}")) {
+        return Err(e);
+    }
+
     if output.status.success() {
         Ok(output)
     } else {
         Err(AocCommandError::BadExitStatus(output))
     }
 }
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::AocCommandError;
+    use crate::template::submissions::Outcome;
+
+    #[test]
+    fn parses_known_responses() {
+        assert!(matches!(
+            AocCommandError::parse("[ERROR aoc] 🔔 Failed to find session cookie"),
+            Some(AocCommandError::NotLoggedIn)
+        ));
+        assert!(matches!(
+            AocCommandError::parse("[ERROR aoc] 🔔 Puzzle 25 of 2030 is not available yet"),
+            Some(AocCommandError::PuzzleLocked)
+        ));
+        assert!(matches!(
+            AocCommandError::parse("You gave an answer too recently. You have 45s left to wait."),
+            Some(AocCommandError::RateLimited {
+                wait_seconds: Some(45)
+            })
+        ));
+        assert!(AocCommandError::parse(
+            "[INFO  aoc] 🎄 aoc-cli - Advent of Code command-line tool"
+        )
+        .is_none());
+
+        let wrong =
+            AocCommandError::parse("That's not the right answer; your answer is too low.").unwrap();
+        assert_eq!(wrong.outcome(), Some(Outcome::TooLow));
+    }
+}
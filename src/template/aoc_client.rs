@@ -0,0 +1,389 @@
+/// Talks to the advent of code website to download inputs and puzzles and to submit answers.
+///
+/// By default, requests are sent by the built-in [`HttpClient`]. Set `AOC_CLIENT=aoc-cli` to use an
+/// installed [aoc-cli](https://github.com/scarvalhojr/aoc-cli) instead.
+use std::{env, error::Error, fmt::Display, fs, io, path::PathBuf, time::Duration};
+
+use crate::template::submissions::Outcome;
+use crate::template::{aoc_cli, markdown, Day, Year};
+
+pub const ENV_CLIENT: &str = "AOC_CLIENT";
+pub const ENV_SESSION: &str = "AOC_SESSION";
+pub const ENV_SESSION_FILE: &str = "AOC_SESSION_FILE";
+pub const ENV_BASE_URL: &str = "AOC_BASE_URL";
+pub const ENV_USER_AGENT: &str = "AOC_USER_AGENT";
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const SESSION_FILE_NAME: &str = ".adventofcode.session";
+
+/// The user agent of requests: `AOC_USER_AGENT` if set, e.g. to add contact details,
+/// otherwise this crate and its `repository` from `Cargo.toml`.
+fn user_agent() -> String {
+    if let Ok(user_agent) = env::var(ENV_USER_AGENT).map(|x| x.trim().to_string()) {
+        if !user_agent.is_empty() {
+            return user_agent;
+        }
+    }
+
+    let name = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
+    match env!("CARGO_PKG_REPOSITORY") {
+        "" => name.to_string(),
+        repository => format!("{name} (+{repository})"),
+    }
+}
+
+/// Whether the user agent tells the website how to reach whoever sends the requests.
+fn has_contact() -> bool {
+    env::var(ENV_USER_AGENT).is_ok_and(|x| !x.trim().is_empty())
+        || !env!("CARGO_PKG_REPOSITORY").is_empty()
+}
+
+/// A backend that can talk to the advent of code website.
+pub trait AocClient {
+    /// The personal puzzle input of a day.
+    fn input(&self, year: Year, day: Day) -> Result<String, AocClientError>;
+
+    /// The puzzle description of a day as Markdown, including part two once it is unlocked.
+    fn puzzle(&self, year: Year, day: Day) -> Result<String, AocClientError>;
+
+    /// Submit an answer and return the response message of the website.
+    fn submit(
+        &self,
+        year: Year,
+        day: Day,
+        part: u8,
+        answer: &str,
+    ) -> Result<String, AocClientError>;
+}
+
+/// The client selected with `AOC_CLIENT`, the built-in [`HttpClient`] by default.
+pub fn client() -> Result<Box<dyn AocClient>, AocClientError> {
+    match env::var(ENV_CLIENT).ok().as_deref() {
+        Some("aoc-cli") => {
+            aoc_cli::check()?;
+            Ok(Box::new(aoc_cli::AocCli))
+        }
+        None | Some("" | "native") => Ok(Box::new(HttpClient::from_env()?)),
+        Some(x) => Err(AocClientError::UnknownClient(x.into())),
+    }
+}
+
+/// Resolve the year a request is for, falling back to `AOC_YEAR`.
+pub fn resolve_year(year: Option<Year>) -> Result<Year, AocClientError> {
+    Year::or_default(year).ok_or(AocClientError::MissingYear)
+}
+
+/// Whether a response to [`AocClient::submit`] confirms that the answer is correct.
+pub fn is_correct_answer(message: &str) -> bool {
+    message.contains("That's the right answer")
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A client that sends requests to the website directly, authenticated with the session cookie.
+pub struct HttpClient {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl HttpClient {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+            agent: ureq::AgentBuilder::new()
+                .user_agent(&user_agent())
+                .timeout(Duration::from_secs(30))
+                .build(),
+        }
+    }
+
+    /// Reads the session cookie from `AOC_SESSION`, or from the file at `AOC_SESSION_FILE`,
+    /// falling back to `~/.adventofcode.session` (the location aoc-cli uses).
+    pub fn from_env() -> Result<Self, AocClientError> {
+        let base_url = env::var(ENV_BASE_URL).unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        if !has_contact() {
+            eprintln!(
+                "Note: requests do not say how to reach you. Set {ENV_USER_AGENT} in `.cargo/config.toml` \
+                or `repository` in `Cargo.toml`, e.g. to `github.com/you/advent-of-code by you@example.com`."
+            );
+        }
+        Ok(Self::new(&base_url, &read_session()?))
+    }
+
+    fn day_url(&self, year: Year, day: Day) -> String {
+        format!("{}/{year}/day/{}", self.base_url, day.into_inner())
+    }
+
+    fn get(&self, url: &str) -> Result<String, AocClientError> {
+        let response = self
+            .agent
+            .get(url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call();
+        read_response(response)
+    }
+}
+
+impl AocClient for HttpClient {
+    fn input(&self, year: Year, day: Day) -> Result<String, AocClientError> {
+        self.get(&format!("{}/input", self.day_url(year, day)))
+    }
+
+    fn puzzle(&self, year: Year, day: Day) -> Result<String, AocClientError> {
+        let html = self.get(&self.day_url(year, day))?;
+        Ok(markdown::puzzle_to_markdown(&html, &self.base_url))
+    }
+
+    fn submit(
+        &self,
+        year: Year,
+        day: Day,
+        part: u8,
+        answer: &str,
+    ) -> Result<String, AocClientError> {
+        let response = self
+            .agent
+            .post(&format!("{}/answer", self.day_url(year, day)))
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(&[("level", &part.to_string()), ("answer", answer)]);
+
+        let html = read_response(response)?;
+        markdown::article_text(&html).ok_or(AocClientError::UnexpectedResponse)
+    }
+}
+
+fn read_response(response: Result<ureq::Response, ureq::Error>) -> Result<String, AocClientError> {
+    match response {
+        Ok(response) => Ok(response.into_string()?),
+        // the website answers requests with an invalid session with a redirect or a 400.
+        Err(ureq::Error::Status(status @ (400 | 401 | 403), _)) => {
+            Err(AocClientError::Unauthorized(status))
+        }
+        Err(ureq::Error::Status(404, _)) => Err(AocClientError::NotFound),
+        Err(ureq::Error::Status(status, _)) => Err(AocClientError::BadStatus(status)),
+        Err(ureq::Error::Transport(e)) => Err(AocClientError::Transport(e.to_string())),
+    }
+}
+
+fn read_session() -> Result<String, AocClientError> {
+    if let Ok(session) = env::var(ENV_SESSION) {
+        if !session.trim().is_empty() {
+            return Ok(session);
+        }
+    }
+
+    let path = env::var(ENV_SESSION_FILE).map(PathBuf::from).or_else(|_| {
+        env::var("HOME")
+            .or_else(|_| env::var("USERPROFILE"))
+            .map(|home| PathBuf::from(home).join(SESSION_FILE_NAME))
+    });
+
+    path.ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .filter(|session| !session.trim().is_empty())
+        .ok_or(AocClientError::MissingSession)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Debug)]
+pub enum AocClientError {
+    MissingSession,
+    MissingYear,
+    UnknownClient(String),
+    Unauthorized(u16),
+    NotFound,
+    BadStatus(u16),
+    UnexpectedResponse,
+    Transport(String),
+    IO(io::Error),
+    AocCli(aoc_cli::AocCommandError),
+}
+
+impl AocClientError {
+    /// The verdict on a submitted answer, for backends that report rejected answers as errors.
+    pub fn outcome(&self) -> Option<Outcome> {
+        match self {
+            AocClientError::AocCli(e) => e.outcome(),
+            _ => None,
+        }
+    }
+}
+
+impl Error for AocClientError {}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::MissingSession => write!(
+                f,
+                "no session cookie found. Set {ENV_SESSION} or save it to ~/{SESSION_FILE_NAME}."
+            ),
+            AocClientError::MissingYear => {
+                write!(f, "no year given. Pass --year or set AOC_YEAR.")
+            }
+            AocClientError::UnknownClient(x) => {
+                write!(
+                    f,
+                    "unknown {ENV_CLIENT} `{x}`, expecting `native` or `aoc-cli`."
+                )
+            }
+            AocClientError::Unauthorized(status) => write!(
+                f,
+                "request was rejected with status {status}. Is the session cookie still valid?"
+            ),
+            AocClientError::NotFound => {
+                write!(f, "puzzle not found. It might not be unlocked yet.")
+            }
+            AocClientError::BadStatus(status) => {
+                write!(f, "request failed with status {status}.")
+            }
+            AocClientError::UnexpectedResponse => {
+                write!(f, "could not read the response of the website.")
+            }
+            AocClientError::Transport(e) => write!(f, "request failed: {e}"),
+            AocClientError::IO(e) => write!(f, "{e}"),
+            AocClientError::AocCli(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<io::Error> for AocClientError {
+    fn from(e: io::Error) -> Self {
+        AocClientError::IO(e)
+    }
+}
+
+impl From<aoc_cli::AocCommandError> for AocClientError {
+    fn from(e: aoc_cli::AocCommandError) -> Self {
+        AocClientError::AocCli(e)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{AocClient, AocClientError, HttpClient};
+    use crate::{day, year};
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread,
+    };
+
+    /// Serve a single request with `status` and `body`, returning the base url and the received request.
+    fn stub_server(
+        status: &'static str,
+        body: &'static str,
+    ) -> (String, thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+            let mut request = String::new();
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(x) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                    content_length = x.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+            let mut content = vec![0; content_length];
+            reader.read_exact(&mut content).unwrap();
+            request.push_str(&String::from_utf8(content).unwrap());
+
+            let mut stream = stream;
+            write!(
+                stream,
+                "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+
+            request
+        });
+
+        (base_url, handle)
+    }
+
+    #[test]
+    fn downloads_inputs() {
+        let (base_url, server) = stub_server("200 OK", "1 2\n3 4\n");
+        let client = HttpClient::new(&base_url, "abc\n");
+
+        assert_eq!(client.input(year!(2023), day!(1)).unwrap(), "1 2\n3 4\n");
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("GET /2023/day/1/input HTTP/1.1"));
+        assert!(request.contains("session=abc\r\n"));
+    }
+
+    #[test]
+    fn converts_puzzles() {
+        let (base_url, server) = stub_server(
+            "200 OK",
+            "<main><article class=\"day-desc\"><h2>--- Day 8 ---</h2><p>Hi.</p></article></main>",
+        );
+        let client = HttpClient::new(&base_url, "abc");
+
+        assert_eq!(
+            client.puzzle(year!(2023), day!(8)).unwrap(),
+            "## --- Day 8 ---\n\nHi.\n"
+        );
+        assert!(server
+            .join()
+            .unwrap()
+            .starts_with("GET /2023/day/8 HTTP/1.1"));
+    }
+
+    #[test]
+    fn submits_answers() {
+        let (base_url, server) = stub_server(
+            "200 OK",
+            "<main><article><p>That's the right answer!</p></article></main>",
+        );
+        let client = HttpClient::new(&base_url, "abc");
+
+        let message = client.submit(year!(2023), day!(2), 1, "42").unwrap();
+        assert!(super::is_correct_answer(&message));
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /2023/day/2/answer HTTP/1.1"));
+        assert!(request.ends_with("level=1&answer=42"));
+    }
+
+    #[test]
+    fn reports_invalid_sessions() {
+        let (base_url, server) = stub_server("400 Bad Request", "");
+        let client = HttpClient::new(&base_url, "expired");
+
+        assert!(matches!(
+            client.input(year!(2023), day!(1)),
+            Err(AocClientError::Unauthorized(400))
+        ));
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn reports_server_errors() {
+        let (base_url, server) = stub_server("500 Internal Server Error", "");
+        let client = HttpClient::new(&base_url, "valid");
+
+        assert!(matches!(
+            client.input(year!(2023), day!(1)),
+            Err(AocClientError::BadStatus(500))
+        ));
+        server.join().unwrap();
+    }
+}
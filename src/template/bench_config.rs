@@ -0,0 +1,392 @@
+/// Settings that control how long and how often solutions are benched.
+///
+/// Values are resolved in order: command-line arguments, environment variables
+/// (e.g. set in `.cargo/config.toml`), built-in defaults.
+use std::{env, error::Error, fmt::Display, str::FromStr, time::Duration};
+
+use crate::template::{Day, Year};
+
+const DEFAULT_BUDGET: Duration = Duration::from_secs(1);
+const DEFAULT_MIN_SAMPLES: u128 = 10;
+const DEFAULT_MAX_SAMPLES: u128 = 10_000;
+
+pub const ENV_BUDGET: &str = "AOC_BENCH_BUDGET";
+pub const ENV_MIN_SAMPLES: &str = "AOC_BENCH_MIN_SAMPLES";
+pub const ENV_MAX_SAMPLES: &str = "AOC_BENCH_MAX_SAMPLES";
+pub const ENV_DAY_BUDGETS: &str = "AOC_BENCH_DAY_BUDGETS";
+pub const ENV_ALLOCATIONS: &str = "AOC_BENCH_ALLOCATIONS";
+
+const ARG_BUDGET: &str = "--budget";
+const ARG_MIN_SAMPLES: &str = "--min-samples";
+const ARG_MAX_SAMPLES: &str = "--max-samples";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BenchConfig {
+    /// Approximate total time to spend on benching a single part.
+    pub budget: Duration,
+    pub min_samples: u128,
+    pub max_samples: u128,
+    /// Budgets that replace `budget` for specific days, of every year or of a single one.
+    pub day_budgets: Vec<DayBudget>,
+    /// Whether solutions are built with the counting allocator of the `alloc-stats` feature.
+    pub count_allocations: bool,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self {
+            budget: DEFAULT_BUDGET,
+            min_samples: DEFAULT_MIN_SAMPLES,
+            max_samples: DEFAULT_MAX_SAMPLES,
+            day_budgets: vec![],
+            count_allocations: false,
+        }
+    }
+}
+
+impl BenchConfig {
+    /// Reads the config from `AOC_BENCH_*` environment variables, falling back to defaults.
+    pub fn from_env() -> Result<Self, BenchConfigError> {
+        let mut config = Self::default();
+
+        if let Ok(x) = env::var(ENV_BUDGET) {
+            config.budget = parse_duration(&x)?;
+        }
+        if let Ok(x) = env::var(ENV_MIN_SAMPLES) {
+            config.min_samples = parse_samples(&x)?;
+        }
+        if let Ok(x) = env::var(ENV_MAX_SAMPLES) {
+            config.max_samples = parse_samples(&x)?;
+        }
+        if let Ok(x) = env::var(ENV_DAY_BUDGETS) {
+            config.day_budgets = x
+                .split(',')
+                .filter(|s| !s.trim().is_empty())
+                .map(str::parse)
+                .collect::<Result<_, _>>()?;
+        }
+        if let Ok(x) = env::var(ENV_ALLOCATIONS) {
+            config.count_allocations = parse_flag(&x)?;
+        }
+
+        config.validate()
+    }
+
+    /// Reads the config of a solution binary from the arguments forwarded by [`BenchConfig::to_args`],
+    /// falling back to the environment for anything that was not passed.
+    pub fn from_args(year: Option<Year>, day: Day) -> Result<Self, BenchConfigError> {
+        let mut config = Self::from_env()?.for_day(year, day);
+        let args: Vec<String> = env::args().collect();
+
+        if let Some(x) = arg_value(&args, ARG_BUDGET) {
+            config.budget = parse_duration(x)?;
+        }
+        if let Some(x) = arg_value(&args, ARG_MIN_SAMPLES) {
+            config.min_samples = parse_samples(x)?;
+        }
+        if let Some(x) = arg_value(&args, ARG_MAX_SAMPLES) {
+            config.max_samples = parse_samples(x)?;
+        }
+
+        config.validate()
+    }
+
+    /// Resolves the per-day override for `day` of `year` into `budget`. The last matching override wins.
+    #[must_use]
+    pub fn for_day(&self, year: Option<Year>, day: Day) -> Self {
+        let budget = self
+            .day_budgets
+            .iter()
+            .rev()
+            .find(|x| x.applies_to(year, day))
+            .map_or(self.budget, |x| x.budget);
+
+        Self {
+            budget,
+            day_budgets: vec![],
+            ..self.clone()
+        }
+    }
+
+    /// Arguments that pass the resolved config for `day` of `year` to a solution binary.
+    pub fn to_args(&self, year: Option<Year>, day: Day) -> Vec<String> {
+        let config = self.for_day(year, day);
+        vec![
+            ARG_BUDGET.into(),
+            format_duration(config.budget),
+            ARG_MIN_SAMPLES.into(),
+            config.min_samples.to_string(),
+            ARG_MAX_SAMPLES.into(),
+            config.max_samples.to_string(),
+        ]
+    }
+
+    /// Environment variables that pass the whole config, including per-day budgets, to a process running several days.
+    pub fn to_env(&self) -> Vec<(&'static str, String)> {
+        let day_budgets: Vec<String> = self
+            .day_budgets
+            .iter()
+            .map(|x| match x.year {
+                Some(year) => format!("{year}-{}={}", x.day, format_duration(x.budget)),
+                None => format!("{}={}", x.day, format_duration(x.budget)),
+            })
+            .collect();
+
+        vec![
+            (ENV_BUDGET, format_duration(self.budget)),
+            (ENV_MIN_SAMPLES, self.min_samples.to_string()),
+            (ENV_MAX_SAMPLES, self.max_samples.to_string()),
+            (ENV_DAY_BUDGETS, day_budgets.join(",")),
+            (ENV_ALLOCATIONS, self.count_allocations.to_string()),
+        ]
+    }
+
+    /// Arguments that make cargo build solutions for this config, i.e. with the `alloc-stats` feature to count allocations.
+    pub fn cargo_args(&self) -> Vec<String> {
+        if self.count_allocations {
+            vec!["--features".into(), "alloc-stats".into()]
+        } else {
+            vec![]
+        }
+    }
+
+    /// Number of bench iterations that fit into the budget, given the duration of a single run.
+    pub fn iterations(&self, base_time: Duration) -> u128 {
+        (self.budget.as_nanos() / base_time.as_nanos().max(10))
+            .clamp(self.min_samples, self.max_samples)
+    }
+
+    /// Checks that the sample bounds are consistent.
+    pub fn validate(self) -> Result<Self, BenchConfigError> {
+        if self.min_samples == 0 || self.min_samples > self.max_samples {
+            return Err(BenchConfigError(format!(
+                "expecting 0 < min samples <= max samples, got {} and {}",
+                self.min_samples, self.max_samples
+            )));
+        }
+        Ok(self)
+    }
+}
+
+fn arg_value<'a>(args: &'a [String], name: &str) -> Option<&'a str> {
+    let index = args.iter().position(|x| x == name)?;
+    args.get(index + 1).map(String::as_str)
+}
+
+fn parse_flag(s: &str) -> Result<bool, BenchConfigError> {
+    match s.trim() {
+        "1" | "true" => Ok(true),
+        "" | "0" | "false" => Ok(false),
+        _ => Err(BenchConfigError(format!(
+            "invalid flag `{s}`, expecting `true` or `false`"
+        ))),
+    }
+}
+
+fn parse_samples(s: &str) -> Result<u128, BenchConfigError> {
+    s.trim()
+        .parse()
+        .map_err(|_| BenchConfigError(format!("invalid sample count `{s}`")))
+}
+
+/// Parses durations like `500ms`, `1.5s`, `200us` or `200µs`. Plain numbers are read as milliseconds.
+pub fn parse_duration(s: &str) -> Result<Duration, BenchConfigError> {
+    let s = s.trim();
+    let err = || {
+        BenchConfigError(format!(
+            "invalid duration `{s}`, expecting e.g. `500ms` or `2s`"
+        ))
+    };
+
+    let (value, unit_secs) = if let Some(x) = s.strip_suffix("ms") {
+        (x, 1e-3)
+    } else if let Some(x) = s.strip_suffix("us").or_else(|| s.strip_suffix("µs")) {
+        (x, 1e-6)
+    } else if let Some(x) = s.strip_suffix("ns") {
+        (x, 1e-9)
+    } else if let Some(x) = s.strip_suffix('s') {
+        (x, 1.0)
+    } else {
+        (s, 1e-3)
+    };
+
+    let value: f64 = value.trim().parse().map_err(|_| err())?;
+    Duration::try_from_secs_f64(value * unit_secs).map_err(|_| err())
+}
+
+fn format_duration(duration: Duration) -> String {
+    format!("{}ns", duration.as_nanos())
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A bench budget for a single day, parsed from `DAY=DURATION`, e.g. `6=100ms`, which applies to
+/// the day of every year, or from `YEAR-DAY=DURATION`, e.g. `2023-6=100ms`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DayBudget {
+    /// [`None`] if the budget applies to every year.
+    pub year: Option<Year>,
+    pub day: Day,
+    pub budget: Duration,
+}
+
+impl DayBudget {
+    fn applies_to(&self, year: Option<Year>, day: Day) -> bool {
+        self.day == day
+            && self
+                .year
+                .is_none_or(|x| Year::scoped(Some(x)) == Year::scoped(year))
+    }
+}
+
+impl FromStr for DayBudget {
+    type Err = BenchConfigError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (key, budget) = s.split_once('=').ok_or_else(|| {
+            BenchConfigError(format!(
+                "invalid day budget `{s}`, expecting e.g. `6=100ms` or `2023-6=100ms`"
+            ))
+        })?;
+        let err = |e: &dyn Display| BenchConfigError(format!("invalid day budget `{s}`: {e}"));
+
+        let (year, day) = match key.split_once('-') {
+            Some((year, day)) => (Some(year.parse().map_err(|e| err(&e))?), day),
+            None => (None, key),
+        };
+
+        Ok(Self {
+            year,
+            day: day.trim().parse().map_err(|e| err(&e))?,
+            budget: parse_duration(budget)?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An error which can be returned when reading a [`BenchConfig`].
+#[derive(Debug)]
+pub struct BenchConfigError(String);
+
+impl Error for BenchConfigError {}
+
+impl Display for BenchConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{parse_duration, BenchConfig, DayBudget};
+    use crate::{day, year};
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse_duration("500ms").unwrap(), Duration::from_millis(500));
+        assert_eq!(parse_duration("1.5s").unwrap(), Duration::from_millis(1500));
+        assert_eq!(parse_duration("200us").unwrap(), Duration::from_micros(200));
+        assert_eq!(parse_duration("200µs").unwrap(), Duration::from_micros(200));
+        assert_eq!(parse_duration("42ns").unwrap(), Duration::from_nanos(42));
+        assert_eq!(parse_duration("250").unwrap(), Duration::from_millis(250));
+        assert!(parse_duration("fast").is_err());
+        assert!(parse_duration("-1s").is_err());
+    }
+
+    #[test]
+    fn parses_day_budgets() {
+        let budget: DayBudget = "6=100ms".parse().unwrap();
+        assert_eq!(budget.year, None);
+        assert_eq!(budget.day, day!(6));
+        assert_eq!(budget.budget, Duration::from_millis(100));
+        let budget: DayBudget = "2023-6=100ms".parse().unwrap();
+        assert_eq!(budget.year, Some(year!(2023)));
+        assert_eq!(budget.day, day!(6));
+        assert!("26=1s".parse::<DayBudget>().is_err());
+        assert!("23-6=1s".parse::<DayBudget>().is_err());
+        assert!("6".parse::<DayBudget>().is_err());
+    }
+
+    #[test]
+    fn resolves_day_overrides() {
+        let config = BenchConfig {
+            day_budgets: vec!["6=100ms".parse().unwrap()],
+            ..BenchConfig::default()
+        };
+        assert_eq!(
+            config.for_day(None, day!(6)).budget,
+            Duration::from_millis(100)
+        );
+        assert_eq!(config.for_day(None, day!(5)).budget, Duration::from_secs(1));
+        assert_eq!(
+            config.to_args(None, day!(6)),
+            [
+                "--budget",
+                "100000000ns",
+                "--min-samples",
+                "10",
+                "--max-samples",
+                "10000"
+            ]
+        );
+        assert!(config
+            .to_env()
+            .contains(&("AOC_BENCH_DAY_BUDGETS", "06=100000000ns".into())));
+    }
+
+    #[test]
+    fn resolves_day_overrides_of_years() {
+        // NOTE: `AOC_YEAR` is set to 2024 in `.cargo/config.toml`.
+        let config = BenchConfig {
+            day_budgets: vec![
+                "2023-6=100ms".parse().unwrap(),
+                "2024-7=5s".parse().unwrap(),
+            ],
+            ..BenchConfig::default()
+        };
+        let budget = |year, day| config.for_day(year, day).budget;
+        assert_eq!(
+            budget(Some(year!(2023)), day!(6)),
+            Duration::from_millis(100)
+        );
+        assert_eq!(budget(None, day!(6)), Duration::from_secs(1));
+        assert_eq!(budget(None, day!(7)), Duration::from_secs(5));
+        assert_eq!(budget(Some(year!(2024)), day!(7)), Duration::from_secs(5));
+        assert!(config.to_env().contains(&(
+            "AOC_BENCH_DAY_BUDGETS",
+            "2023-06=100000000ns,2024-07=5000000000ns".into()
+        )));
+    }
+
+    #[test]
+    fn builds_with_counting_allocator() {
+        assert!(BenchConfig::default().cargo_args().is_empty());
+        let config = BenchConfig {
+            count_allocations: true,
+            ..BenchConfig::default()
+        };
+        assert_eq!(config.cargo_args(), ["--features", "alloc-stats"]);
+        assert!(config
+            .to_env()
+            .contains(&("AOC_BENCH_ALLOCATIONS", "true".into())));
+    }
+
+    #[test]
+    fn clamps_iterations() {
+        let config = BenchConfig {
+            budget: Duration::from_millis(100),
+            min_samples: 2,
+            max_samples: 50,
+            day_budgets: vec![],
+            count_allocations: false,
+        };
+        assert_eq!(config.iterations(Duration::from_secs(10)), 2);
+        assert_eq!(config.iterations(Duration::from_millis(10)), 10);
+        assert_eq!(config.iterations(Duration::from_nanos(1)), 50);
+    }
+}
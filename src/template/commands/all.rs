@@ -1,5 +1,67 @@
-use crate::template::{all_days, run_multi::run_multi};
+use std::process::{Command, Stdio};
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+use crate::template::{all_days, bench_config::BenchConfig, run_multi::run_multi, Year};
+
+pub fn handle(
+    year: Option<Year>,
+    is_release: bool,
+    is_timed: bool,
+    bench_config: &BenchConfig,
+    in_process: bool,
+    jobs: usize,
+) {
+    if jobs > 1 && is_timed {
+        eprintln!("Note: `--time` runs days sequentially, ignoring `--jobs`.");
+    }
+
+    if in_process {
+        if jobs > 1 {
+            eprintln!("Note: `--in-process` runs days sequentially, ignoring `--jobs`.");
+        }
+        run_in_process(year, is_release, bench_config);
+    } else {
+        run_multi(
+            year,
+            &all_days().collect(),
+            is_release,
+            is_timed,
+            bench_config,
+            jobs,
+        );
+    }
+}
+
+/// Run all days within the single `all` binary, which shares one build instead of invoking cargo per day.
+fn run_in_process(year: Option<Year>, is_release: bool, bench_config: &BenchConfig) {
+    let mut cmd_args = vec![
+        "run".to_string(),
+        "--quiet".to_string(),
+        "--bin".to_string(),
+        "all".to_string(),
+        "--features".to_string(),
+        "registry".to_string(),
+    ];
+
+    if is_release {
+        cmd_args.push("--release".to_string());
+    }
+
+    cmd_args.extend(bench_config.cargo_args());
+
+    cmd_args.push("--".to_string());
+
+    if let Some(year) = year {
+        cmd_args.push("--year".to_string());
+        cmd_args.push(year.to_string());
+    }
+
+    let mut cmd = Command::new("cargo")
+        .args(&cmd_args)
+        .envs(bench_config.to_env())
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .unwrap();
+
+    cmd.wait().unwrap();
 }
@@ -1,14 +1,42 @@
-use crate::template::{aoc_cli, Day};
-use std::process;
+use std::{fs, path::Path, process};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+use crate::template::aoc_client::{self, AocClientError};
+use crate::template::{data_dir, Day, Year};
+
+pub fn handle(year: Option<Year>, day: Day) {
+    if let Err(e) = download(year, day) {
+        eprintln!("Failed to download day {day}: {e}");
         process::exit(1);
     }
+}
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
-    };
+fn download(year: Option<Year>, day: Day) -> Result<(), AocClientError> {
+    let client = aoc_client::client()?;
+    let resolved_year = aoc_client::resolve_year(year)?;
+
+    let data_dir = data_dir(year);
+    let input_path = data_dir.join("inputs").join(format!("{day}.txt"));
+    let puzzle_path = data_dir.join("puzzles").join(format!("{day}.md"));
+
+    write_file(&input_path, &client.input(resolved_year, day)?)?;
+    write_file(&puzzle_path, &client.puzzle(resolved_year, day)?)?;
+
+    println!("---");
+    println!(
+        "🎄 Successfully wrote input to \"{}\".",
+        input_path.display()
+    );
+    println!(
+        "🎄 Successfully wrote puzzle to \"{}\".",
+        puzzle_path.display()
+    );
+    Ok(())
+}
+
+pub(crate) fn write_file(path: &Path, content: &str) -> Result<(), AocClientError> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, content)?;
+    Ok(())
 }
@@ -0,0 +1,106 @@
+use std::{fs, process};
+
+use crate::template::examples::{self, CodeBlock};
+use crate::template::{data_dir, Day, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+/// Lines of a code block shown by `--list`.
+const PREVIEW_LINES: usize = 3;
+
+/// Write the example inputs of a day, extracted from its puzzle description, to `data/examples`.
+/// `picks` are 1-based indices as shown by `list`. Without them, the examples are picked heuristically.
+pub fn handle(year: Option<Year>, day: Day, list: bool, picks: &[usize], overwrite: bool) {
+    let puzzle_path = data_dir(year).join("puzzles").join(format!("{day}.md"));
+    let Ok(puzzle) = fs::read_to_string(&puzzle_path) else {
+        eprintln!(
+            "Failed to read \"{}\". Download the puzzle with `cargo download {day}` first.",
+            puzzle_path.display()
+        );
+        process::exit(1);
+    };
+
+    let blocks = examples::code_blocks(&puzzle);
+
+    if blocks.is_empty() {
+        eprintln!("The puzzle description of day {day} contains no code blocks.");
+        process::exit(1);
+    }
+
+    if list {
+        print_blocks(&blocks);
+        return;
+    }
+
+    let selected: Vec<&CodeBlock> = if picks.is_empty() {
+        examples::select(&blocks)
+    } else {
+        picks
+            .iter()
+            .map(|i| {
+                i.checked_sub(1)
+                    .and_then(|i| blocks.get(i))
+                    .unwrap_or_else(|| {
+                        eprintln!(
+                            "Unknown code block {i}, expecting 1 to {}. Use `--list` to show them.",
+                            blocks.len()
+                        );
+                        process::exit(1);
+                    })
+            })
+            .collect()
+    };
+
+    let examples_dir = data_dir(year).join("examples");
+    if let Err(e) = fs::create_dir_all(&examples_dir) {
+        eprintln!("Failed to create examples directory: {e}");
+        process::exit(1);
+    }
+
+    for (i, block) in selected.into_iter().enumerate() {
+        let path = examples_dir.join(examples::file_name(day, i));
+
+        // scaffolded example files are empty and can be replaced safely.
+        let has_content = fs::read_to_string(&path).is_ok_and(|x| !x.trim().is_empty());
+        if has_content && !overwrite {
+            println!(
+                "Skipped \"{}\": file is not empty. Pass --overwrite to replace it.",
+                path.display()
+            );
+            continue;
+        }
+
+        match fs::write(&path, &block.content) {
+            Ok(()) => println!(
+                "🎄 Wrote example ({} lines, part {}) to \"{}\".",
+                block.line_count(),
+                block.part,
+                path.display()
+            ),
+            Err(e) => {
+                eprintln!("Failed to write example: {e}");
+                process::exit(1);
+            }
+        }
+    }
+}
+
+fn print_blocks(blocks: &[CodeBlock]) {
+    let picks = examples::select(blocks);
+
+    for (i, block) in blocks.iter().enumerate() {
+        let is_pick = picks.iter().any(|x| std::ptr::eq(*x, block));
+        println!(
+            "{ANSI_BOLD}[{}]{ANSI_RESET} part {}, {} lines{}",
+            i + 1,
+            block.part,
+            block.line_count(),
+            if is_pick { " (picked by default)" } else { "" }
+        );
+
+        for line in block.content.lines().take(PREVIEW_LINES) {
+            println!("    {line}");
+        }
+        if block.line_count() > PREVIEW_LINES {
+            println!("    {ANSI_ITALIC}...{ANSI_RESET}");
+        }
+    }
+}
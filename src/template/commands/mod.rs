@@ -1,6 +1,10 @@
 pub mod all;
 pub mod download;
+pub mod examples;
+pub mod profile;
 pub mod read;
 pub mod scaffold;
 pub mod solve;
+pub mod sync_tests;
 pub mod time;
+pub mod verify;
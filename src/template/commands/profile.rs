@@ -0,0 +1,96 @@
+use std::process::{self, Command, Stdio};
+use std::{env, fs};
+
+use crate::template::flamegraph;
+use crate::template::{bin_name, data_dir, Day, InputSource, Year};
+
+/// Samples per second that `perf` records. Not a round number, so that it does not run in lockstep with timers.
+const SAMPLE_FREQUENCY: &str = "997";
+
+/// Record a CPU profile of a day with `perf` and render it as a flamegraph to `data/profiles/NN.svg`.
+pub fn handle(year: Option<Year>, day: Day, input: &InputSource) {
+    if !Command::new("perf")
+        .arg("--version")
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|x| x.success())
+    {
+        eprintln!("CPU profiles are recorded with `perf`, which was not found. Install it, e.g. with `apt install linux-tools-generic` on Ubuntu.");
+        process::exit(1);
+    }
+
+    let bin = bin_name(year, day);
+
+    let build = Command::new("cargo")
+        .args(["build", "--quiet", "--profile", "profiling", "--bin", &bin])
+        .status();
+    if !build.is_ok_and(|x| x.success()) {
+        eprintln!("Failed to build day {day}.");
+        process::exit(1);
+    }
+
+    let target_dir = env::var("CARGO_TARGET_DIR").unwrap_or_else(|_| "target".into());
+    let bin_path = format!("{target_dir}/profiling/{bin}");
+    let data_path = env::temp_dir().join(format!("aoc-{bin}.perf.data"));
+
+    let record = Command::new("perf")
+        .args([
+            "record",
+            "--quiet",
+            "-F",
+            SAMPLE_FREQUENCY,
+            "--call-graph",
+            "dwarf",
+            "-o",
+        ])
+        .arg(&data_path)
+        .arg("--")
+        .arg(&bin_path)
+        .args(input.to_args())
+        .status();
+    if !record.is_ok_and(|x| x.success()) {
+        eprintln!("Failed to record a profile of day {day}. If perf lacks permissions, lower `/proc/sys/kernel/perf_event_paranoid`.");
+        process::exit(1);
+    }
+
+    let script = Command::new("perf")
+        .args(["script", "-i"])
+        .arg(&data_path)
+        .stderr(Stdio::null())
+        .output();
+    let _ = fs::remove_file(&data_path);
+
+    let script = match script {
+        Ok(output) if output.status.success() => {
+            String::from_utf8_lossy(&output.stdout).into_owned()
+        }
+        _ => {
+            eprintln!("Failed to read the profile of day {day} with `perf script`.");
+            process::exit(1);
+        }
+    };
+
+    let folded = flamegraph::fold_perf_script(&script);
+    let samples: u64 = folded.values().sum();
+    if samples == 0 {
+        eprintln!(
+            "The profile of day {day} has no samples. Is the solution too fast to be sampled?"
+        );
+        process::exit(1);
+    }
+
+    let dir = data_dir(year).join("profiles");
+    let path = dir.join(format!("{day}.svg"));
+    let svg = flamegraph::render_svg(&folded, &format!("Day {day}"));
+
+    if let Err(e) = fs::create_dir_all(&dir).and_then(|()| fs::write(&path, svg)) {
+        eprintln!("Failed to write flamegraph: {e}");
+        process::exit(1);
+    }
+
+    println!(
+        "🎄 Wrote flamegraph of {samples} samples to \"{}\".",
+        path.display()
+    );
+}
@@ -1,15 +1,24 @@
 use std::process;
 
-use crate::template::{aoc_cli, Day};
+use crate::template::aoc_client::{self, AocClientError};
+use crate::template::commands::download::write_file;
+use crate::template::{data_dir, Day, Year};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+pub fn handle(year: Option<Year>, day: Day) {
+    if let Err(e) = read(year, day) {
+        eprintln!("Failed to read day {day}: {e}");
         process::exit(1);
     }
+}
 
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
-    };
+/// Fetch the latest puzzle description, e.g. to read part two, store it and print it to the terminal.
+fn read(year: Option<Year>, day: Day) -> Result<(), AocClientError> {
+    let client = aoc_client::client()?;
+    let puzzle = client.puzzle(aoc_client::resolve_year(year)?, day)?;
+
+    let puzzle_path = data_dir(year).join("puzzles").join(format!("{day}.md"));
+    write_file(&puzzle_path, &puzzle)?;
+
+    println!("{puzzle}");
+    Ok(())
 }
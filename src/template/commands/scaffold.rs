@@ -1,14 +1,21 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, create_dir_all, File, OpenOptions},
     io::Write,
+    path::PathBuf,
     process,
 };
 
-use crate::template::Day;
+use crate::template::{bin_path, data_dir, Day, Year};
 
 const MODULE_TEMPLATE: &str =
     include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
 
+/// Directory of user templates, e.g. `templates/grid.rs.tpl`.
+const TEMPLATES_DIR: &str = "templates";
+/// User template that replaces the built-in one when no `--template` is passed.
+const DEFAULT_TEMPLATE_NAME: &str = "default";
+const DEFAULT_ANSWER_TYPE: &str = "u32";
+
 fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
     let mut file = OpenOptions::new();
     if overwrite {
@@ -27,10 +34,98 @@ fn create_file(path: &str) -> Result<File, std::io::Error> {
         .open(path)
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+/// The year passed to `solution!` for years other than the default one, e.g. `, year = 2023`.
+fn year_argument(year: Option<Year>) -> String {
+    Year::scoped(year).map_or_else(String::new, |year| format!(", year = {year}"))
+}
+
+fn template_path(name: &str) -> PathBuf {
+    PathBuf::from(TEMPLATES_DIR).join(format!("{name}.rs.tpl"))
+}
+
+/// The template named `name`, or the default one: `templates/default.rs.tpl` if present, the built-in one otherwise.
+fn load_template(name: Option<&str>) -> Result<String, String> {
+    match name {
+        Some(name) => {
+            let path = template_path(name);
+            fs::read_to_string(&path)
+                .map_err(|e| format!("could not read template \"{}\": {e}", path.display()))
+        }
+        None => Ok(fs::read_to_string(template_path(DEFAULT_TEMPLATE_NAME))
+            .unwrap_or_else(|_| MODULE_TEMPLATE.to_string())),
+    }
+}
+
+/// The title of a puzzle, e.g. `Historian Hysteria` for `## --- Day 1: Historian Hysteria ---`.
+fn puzzle_title(markdown: &str) -> Option<String> {
+    let heading = markdown
+        .lines()
+        .find(|line| line.starts_with("## --- Day"))?;
+    let (_, title) = heading.split_once(": ")?;
+    Some(title.trim_end_matches('-').trim().to_string())
+}
+
+/// Values of the placeholders a template can use.
+struct Placeholders {
+    day: Day,
+    year: Option<Year>,
+    title: String,
+    answer_type: String,
+}
+
+impl Placeholders {
+    fn render(&self, template: &str) -> String {
+        let year = Year::or_default(self.year).map_or_else(String::new, |year| year.to_string());
+
+        template
+            .replace("%DAY_NUMBER%", &self.day.into_inner().to_string())
+            .replace("%DAY%", &self.day.to_string())
+            .replace("%YEAR_ARGUMENT%", &year_argument(self.year))
+            .replace("%YEAR%", &year)
+            .replace("%TITLE%", &self.title)
+            .replace("%ANSWER_TYPE%", &self.answer_type)
+    }
+}
+
+pub fn handle(
+    year: Option<Year>,
+    day: Day,
+    overwrite: bool,
+    template: Option<&str>,
+    answer_type: Option<&str>,
+) {
+    let data_dir = data_dir(year);
+    let input_path = data_dir
+        .join(format!("inputs/{day}.txt"))
+        .display()
+        .to_string();
+    let example_path = data_dir
+        .join(format!("examples/{day}.txt"))
+        .display()
+        .to_string();
+    let module_path = bin_path(year, day);
+
+    for dir in ["inputs", "examples", "puzzles"] {
+        if let Err(e) = create_dir_all(data_dir.join(dir)) {
+            eprintln!("Failed to create data directory: {e}");
+            process::exit(1);
+        }
+    }
+
+    let template = load_template(template).unwrap_or_else(|e| {
+        eprintln!("Failed to load template: {e}");
+        process::exit(1);
+    });
+
+    let placeholders = Placeholders {
+        day,
+        year,
+        title: fs::read_to_string(data_dir.join(format!("puzzles/{day}.md")))
+            .ok()
+            .and_then(|puzzle| puzzle_title(&puzzle))
+            .unwrap_or_default(),
+        answer_type: answer_type.unwrap_or(DEFAULT_ANSWER_TYPE).to_string(),
+    };
 
     let mut file = match safe_create_file(&module_path, overwrite) {
         Ok(file) => file,
@@ -40,11 +135,7 @@ pub fn handle(day: Day, overwrite: bool) {
         }
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
+    match file.write_all(placeholders.render(&template).as_bytes()) {
         Ok(()) => {
             println!("Created module file \"{}\"", &module_path);
         }
@@ -75,5 +166,68 @@ pub fn handle(day: Day, overwrite: bool) {
     }
 
     println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    match Year::scoped(year) {
+        Some(year) => println!("🎄 Type `cargo solve {day} --year {year}` to run your solution."),
+        None => println!("🎄 Type `cargo solve {day}` to run your solution."),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{puzzle_title, Placeholders, MODULE_TEMPLATE};
+    use crate::{day, year};
+
+    /// Scaffolded tests read the empty example file and expect `None`, so they pass until the example is pasted.
+    #[test]
+    fn renders_templates_with_example_tests() {
+        let placeholders = Placeholders {
+            day: day!(25),
+            year: None,
+            title: "Code Chronicle".into(),
+            answer_type: "u32".into(),
+        };
+        let grid_template = include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/templates/grid.rs.tpl"
+        ));
+
+        for template in [MODULE_TEMPLATE, grid_template] {
+            let source = placeholders.render(template);
+            assert!(!source.contains('%'), "{source}");
+            assert!(source.contains("advent_of_code::solution!(25);"));
+            assert_eq!(
+                source
+                    .matches(r#"read_year_file("examples", YEAR, DAY)"#)
+                    .count(),
+                2
+            );
+            assert_eq!(source.matches("assert_eq!(result, None);").count(), 2);
+        }
+    }
+
+    #[test]
+    fn renders_placeholders() {
+        let placeholders = Placeholders {
+            day: day!(9),
+            year: Some(year!(2023)),
+            title: "Disk Fragmenter".into(),
+            answer_type: "u64".into(),
+        };
+
+        assert_eq!(
+            placeholders.render("// %YEAR% day %DAY%: %TITLE%\nsolution!(%DAY_NUMBER%%YEAR_ARGUMENT%);\nOption<%ANSWER_TYPE%>"),
+            "// 2023 day 09: Disk Fragmenter\nsolution!(9, year = 2023);\nOption<u64>"
+        );
+    }
+
+    #[test]
+    fn reads_puzzle_titles() {
+        assert_eq!(
+            puzzle_title("## --- Day 1: Historian Hysteria ---\n\nText.").as_deref(),
+            Some("Historian Hysteria")
+        );
+        assert_eq!(puzzle_title("no title"), None);
+    }
 }
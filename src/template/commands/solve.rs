@@ -1,9 +1,28 @@
-use std::process::{Command, Stdio};
+use std::fs;
+use std::path::Path;
+use std::process::{self, Command, Stdio};
 
-use crate::template::Day;
+use crate::template::heap_profile::HeapSummary;
+use crate::template::multi_input;
+use crate::template::{
+    bench_config::BenchConfig, bin_name, Day, InputSource, Year, ANSI_BOLD, ANSI_RESET,
+};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+/// The file dhat writes its profile to, relative to the working directory.
+const DHAT_FILE_NAME: &str = "dhat-heap.json";
+
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    year: Option<Year>,
+    day: Day,
+    release: bool,
+    dhat: bool,
+    count_allocations: bool,
+    submit_part: Option<u8>,
+    bench_config: Option<&BenchConfig>,
+    input: &InputSource,
+) {
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), bin_name(year, day)];
 
     if dhat {
         cmd_args.extend([
@@ -16,8 +35,19 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push("--release".to_string());
     }
 
+    if count_allocations {
+        cmd_args.extend(["--features".to_string(), "alloc-stats".to_string()]);
+    }
+
     cmd_args.push("--".to_string());
 
+    if let Some(bench_config) = bench_config {
+        cmd_args.push("--time".to_string());
+        cmd_args.extend(bench_config.to_args(year, day));
+    }
+
+    cmd_args.extend(input.to_args());
+
     if let Some(submit_part) = submit_part {
         cmd_args.push("--submit".to_string());
         cmd_args.push(submit_part.to_string());
@@ -31,4 +61,44 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         .unwrap();
 
     cmd.wait().unwrap();
+
+    if dhat {
+        print_heap_summary();
+    }
+}
+
+/// Print a summary of the heap profile that dhat wrote to the working directory.
+fn print_heap_summary() {
+    let Ok(json) = fs::read_to_string(DHAT_FILE_NAME) else {
+        return;
+    };
+
+    match json.parse::<HeapSummary>() {
+        Ok(summary) => {
+            println!();
+            println!("{ANSI_BOLD}Heap profile{ANSI_RESET} ({DHAT_FILE_NAME})");
+            println!("------");
+            print!("{summary}");
+        }
+        Err(e) => eprintln!("Failed to read {DHAT_FILE_NAME}: {e}"),
+    }
+}
+
+/// Run both parts of a day against every input in `dir` and print a table of the answers.
+/// Answers are checked against the expected answers stored next to the inputs, if any.
+pub fn handle_inputs_dir(
+    year: Option<Year>,
+    day: Day,
+    dir: &Path,
+    release: bool,
+    is_timed: bool,
+    bench_config: &BenchConfig,
+) {
+    let runs =
+        multi_input::run(year, day, dir, release, is_timed, bench_config).unwrap_or_else(|e| {
+            eprintln!("{e}");
+            process::exit(1);
+        });
+
+    print!("{}", multi_input::format_table(&runs));
 }
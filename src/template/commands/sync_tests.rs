@@ -0,0 +1,208 @@
+use std::{fs, path::Path, process};
+
+use crate::template::examples;
+use crate::template::{bin_path, data_dir, Day, Year};
+
+/// Assertions of a scaffolded test that has not been filled in yet, the latter for parts that return a [`Result`].
+const PLACEHOLDERS: [&str; 2] = ["assert_eq!(result, None);", "assert_eq!(result, Ok(None));"];
+
+/// Fill the expected example answers, as stated in the puzzle description, into the tests of a solution.
+/// Only tests that still assert the scaffolded `None` are changed.
+pub fn handle(year: Option<Year>, day: Day) {
+    let puzzle_path = data_dir(year).join("puzzles").join(format!("{day}.md"));
+    let Ok(puzzle) = fs::read_to_string(&puzzle_path) else {
+        eprintln!(
+            "Failed to read \"{}\". Download the puzzle with `cargo download {day}` first.",
+            puzzle_path.display()
+        );
+        process::exit(1);
+    };
+
+    let module_path = bin_path(year, day);
+    let Ok(mut source) = fs::read_to_string(&module_path) else {
+        eprintln!("Failed to read module file \"{module_path}\".");
+        process::exit(1);
+    };
+
+    let has_second_example = data_dir(year)
+        .join("examples")
+        .join(examples::file_name(day, 1))
+        .exists();
+
+    let mut filled = vec![];
+
+    for (i, answer) in examples::example_answers(&puzzle).into_iter().enumerate() {
+        let Some(answer) = answer else {
+            continue;
+        };
+        let part = i + 1;
+        let test_name = ["test_part_one", "test_part_two"][i];
+        let return_type = return_type(&source, ["part_one", "part_two"][i]).unwrap_or("Option");
+
+        if let Some(updated) =
+            fill_expected(&source, test_name, &expected_literal(&answer, return_type))
+        {
+            source = updated;
+            if part == 2 && has_second_example {
+                source = use_second_example(&source);
+            }
+            filled.push(format!("part {part}: {answer}"));
+        }
+    }
+
+    if filled.is_empty() {
+        println!("No tests to fill in for day {day}.");
+        return;
+    }
+
+    if let Err(e) = fs::write(Path::new(&module_path), source) {
+        eprintln!("Failed to write module file: {e}");
+        process::exit(1);
+    }
+
+    println!(
+        "🎄 Filled in example answers of \"{module_path}\" ({}).",
+        filled.join(", ")
+    );
+}
+
+/// The expected value of a part as Rust code: a number as is, anything else as a string.
+/// Wrapped into `Some(..)`, `Ok(..)` or `Ok(Some(..))` to match the `return_type` of the part.
+fn expected_literal(answer: &str, return_type: &str) -> String {
+    let value = if answer.parse::<i128>().is_ok() {
+        answer.to_string()
+    } else {
+        format!("{answer:?}.to_string()")
+    };
+
+    if return_type.starts_with("Result<Option<") {
+        format!("Ok(Some({value}))")
+    } else if return_type.starts_with("Result<") {
+        format!("Ok({value})")
+    } else {
+        format!("Some({value})")
+    }
+}
+
+/// The return type of the function `name`, e.g. `Option<u32>` for `pub fn part_one(input: &str) -> Option<u32> {`.
+fn return_type<'a>(source: &'a str, name: &str) -> Option<&'a str> {
+    let start = source.find(&format!("fn {name}("))?;
+    let signature = &source[start..start + source[start..].find('{')?];
+    Some(signature.split_once("->")?.1.trim())
+}
+
+/// Replace the placeholder assertion of the test function `test_name`, if it still has one.
+fn fill_expected(source: &str, test_name: &str, expected: &str) -> Option<String> {
+    let start = source.find(&format!("fn {test_name}()"))?;
+    // the body of the test ends where the next function starts.
+    let end = source[start + 1..]
+        .find("fn ")
+        .map_or(source.len(), |i| start + 1 + i);
+
+    let (offset, placeholder) = PLACEHOLDERS
+        .iter()
+        .find_map(|x| Some((start + source[start..end].find(x)?, x)))?;
+
+    let mut updated = source.to_string();
+    updated.replace_range(
+        offset..offset + placeholder.len(),
+        &format!("assert_eq!(result, {expected});"),
+    );
+    Some(updated)
+}
+
+/// Point the test of part two to the second example file, e.g. `01-2.txt`.
+fn use_second_example(source: &str) -> String {
+    let Some(start) = source.find("fn test_part_two()") else {
+        return source.to_string();
+    };
+
+    let (head, tail) = source.split_at(start);
+    let tail = tail
+        .replacen(
+            r#"read_year_file("examples", YEAR, DAY)"#,
+            r#"read_year_file_part("examples", YEAR, DAY, 2)"#,
+            1,
+        )
+        .replacen(
+            r#"read_file("examples", DAY)"#,
+            r#"read_file_part("examples", DAY, 2)"#,
+            1,
+        );
+
+    format!("{head}{tail}")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{expected_literal, fill_expected, return_type, use_second_example};
+
+    const SOURCE: &str = r#"#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_year_file("examples", YEAR, DAY));
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_year_file("examples", YEAR, DAY));
+        assert_eq!(result, None);
+    }
+}
+"#;
+
+    #[test]
+    fn fills_placeholders() {
+        let source = fill_expected(SOURCE, "test_part_two", "Some(31)").unwrap();
+        assert!(source.contains("assert_eq!(result, None);"));
+        assert!(source.contains("assert_eq!(result, Some(31));"));
+
+        let source = fill_expected(&source, "test_part_one", "Some(11)").unwrap();
+        assert!(!source.contains("None"));
+        assert_eq!(fill_expected(&source, "test_part_one", "Some(12)"), None);
+    }
+
+    #[test]
+    fn formats_expected_values() {
+        assert_eq!(expected_literal("-42", "Option<i64>"), "Some(-42)");
+        assert_eq!(
+            expected_literal("CMZ", "Option<String>"),
+            r#"Some("CMZ".to_string())"#
+        );
+        assert_eq!(expected_literal("11", "Result<u32, ParseError>"), "Ok(11)");
+        assert_eq!(
+            expected_literal("11", "Result<Option<u32>, ParseError>"),
+            "Ok(Some(11))"
+        );
+    }
+
+    #[test]
+    fn fills_placeholders_of_result_parts() {
+        let source = format!(
+            "pub fn part_one(input: &str) -> Result<u32, ParseError> {{\n    todo!()\n}}\n\n{SOURCE}"
+        );
+        let return_type = return_type(&source, "part_one").unwrap();
+        assert_eq!(return_type, "Result<u32, ParseError>");
+
+        let expected = expected_literal("11", return_type);
+        let source = fill_expected(&source, "test_part_one", &expected).unwrap();
+        assert!(source.contains("assert_eq!(result, Ok(11));"));
+
+        let source = source.replace("assert_eq!(result, None);", "assert_eq!(result, Ok(None));");
+        let source = fill_expected(&source, "test_part_two", "Ok(31)").unwrap();
+        assert!(source.contains("assert_eq!(result, Ok(31));"));
+    }
+
+    #[test]
+    fn switches_part_two_to_second_example() {
+        let source = use_second_example(SOURCE);
+        assert_eq!(source.matches("read_year_file(").count(), 1);
+        assert!(source.contains(r#"read_year_file_part("examples", YEAR, DAY, 2)"#));
+    }
+}
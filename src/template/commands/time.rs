@@ -1,15 +1,35 @@
-use std::collections::HashSet;
+use std::{
+    collections::HashSet,
+    env, process,
+    process::Command,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 
+use crate::template::bench_config::BenchConfig;
+use crate::template::compare;
 use crate::template::run_multi::run_multi;
 use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::{all_days, readme_benchmarks, Day, Year, ANSI_BOLD, ANSI_RESET};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
+pub fn handle(
+    year: Option<Year>,
+    day: Option<Day>,
+    run_all: bool,
+    store: bool,
+    show_stats: bool,
+    bench_config: &BenchConfig,
+    compare_threshold: Option<f64>,
+) {
+    // stored timings are merged into on `--store`, so a broken file must not be replaced.
+    let stored_timings = Timings::read_from_file(year).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        process::exit(1);
+    });
 
     let days_to_run = day.map_or_else(
         || {
-            if run_all {
+            // comparing re-runs every day so that stored timings can be checked.
+            if run_all || compare_threshold.is_some() {
                 all_days().collect()
             } else {
                 // when the `--all` flag is not set, filter out days that are fully benched.
@@ -21,14 +41,26 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let timings = run_multi(year, &days_to_run, true, true, bench_config, 1).unwrap();
+
+    let has_regressions = compare_threshold.is_some_and(|threshold| {
+        let comparisons = compare::compare(&stored_timings, &timings);
+        compare::print_comparisons(&comparisons, threshold)
+    });
 
     if store {
+        let mut timings = timings;
+        timings.record_history(
+            current_timestamp(),
+            current_commit().as_deref(),
+            machine_label().as_deref(),
+        );
+
         let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+        merged_timings.store_file(year).unwrap();
 
         println!();
-        match readme_benchmarks::update(merged_timings) {
+        match readme_benchmarks::update(merged_timings, show_stats, year) {
             Ok(()) => {
                 println!("Stored updated benchmarks.");
             }
@@ -37,4 +69,113 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
             }
         }
     }
+
+    if has_regressions {
+        process::exit(1);
+    }
+}
+
+/// Print every stored benchmark run of `day` in chronological order.
+pub fn handle_history(year: Option<Year>, day: Day) {
+    let timings = Timings::read_from_file(year).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        process::exit(1);
+    });
+    let entries = timings.history_for_day(day);
+
+    println!("{ANSI_BOLD}Day {day} history{ANSI_RESET}");
+    println!("------");
+
+    if entries.is_empty() {
+        println!("No stored runs. Use `cargo time {day} --store` to record one.");
+        return;
+    }
+
+    for entry in entries {
+        println!(
+            "{}  {:<9} {:<16} Part 1: {:<10} Part 2: {}",
+            format_timestamp(entry.timestamp),
+            entry.commit.as_deref().unwrap_or("-"),
+            entry.machine.as_deref().unwrap_or("-"),
+            format_nanos(entry.part_1_nanos),
+            format_nanos(entry.part_2_nanos),
+        );
+    }
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn format_nanos(nanos: Option<f64>) -> String {
+    nanos.map_or_else(
+        || "-".into(),
+        |x| format!("{:.1?}", Duration::from_nanos(x as u64)),
+    )
+}
+
+/// Formats seconds since the unix epoch as `YYYY-MM-DD HH:MM UTC`.
+/// See: https://howardhinnant.github.io/date_algorithms.html#civil_from_days
+#[allow(clippy::cast_possible_wrap, clippy::cast_sign_loss)]
+fn format_timestamp(timestamp: u64) -> String {
+    let days = (timestamp / 86_400) as i64;
+    let secs_of_day = timestamp % 86_400;
+
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02} UTC",
+        secs_of_day / 3_600,
+        secs_of_day % 3_600 / 60
+    )
+}
+
+fn current_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/// Abbreviated hash of `HEAD`, if run inside a git repository.
+fn current_commit() -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let hash = String::from_utf8(output.stdout).ok()?.trim().to_string();
+    (!hash.is_empty()).then_some(hash)
+}
+
+/// Label of the current machine, taken from `AOC_MACHINE` or the hostname.
+fn machine_label() -> Option<String> {
+    env::var("AOC_MACHINE")
+        .ok()
+        .or_else(|| {
+            let output = Command::new("hostname").output().ok()?;
+            String::from_utf8(output.stdout).ok()
+        })
+        .map(|x| x.trim().to_string())
+        .filter(|x| !x.is_empty())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::format_timestamp;
+
+    #[test]
+    fn formats_timestamps() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00 UTC");
+        assert_eq!(format_timestamp(1_733_500_000), "2024-12-06 15:46 UTC");
+        assert_eq!(format_timestamp(951_782_400), "2000-02-29 00:00 UTC");
+    }
 }
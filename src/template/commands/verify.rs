@@ -0,0 +1,138 @@
+use std::{collections::HashSet, path::Path, process};
+
+use crate::template::answers::{self, Verdict};
+use crate::template::bench_config::BenchConfig;
+use crate::template::multi_input;
+use crate::template::report::PartReport;
+use crate::template::run_multi::run_multi_reports;
+use crate::template::{all_days, Day, Year, ANSI_BOLD, ANSI_GREEN, ANSI_RED, ANSI_RESET};
+
+/// Re-run solutions and check their answers against the ones stored in `data/answers`.
+/// Answers are stored when a submission is accepted, or with `record` for parts that have no stored answer yet.
+/// Other runs never store answers, as their correctness is unknown.
+pub fn handle(year: Option<Year>, day: Option<Day>, is_release: bool, record: bool) {
+    let days_to_run = day.map_or_else(|| all_days().collect(), |day| HashSet::from([day]));
+    let results = run_multi_reports(
+        year,
+        &days_to_run,
+        is_release,
+        false,
+        &BenchConfig::default(),
+        1,
+    );
+
+    println!();
+    println!("{ANSI_BOLD}Verification{ANSI_RESET}");
+    println!("------");
+
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+
+    for (day, reports) in &results {
+        // every part with a stored answer is checked, even if the solution did not report it,
+        // e.g. because the input is missing or the binary crashed before running the part.
+        for part in [1, 2] {
+            let report = reports.iter().find(|x| x.part == part);
+            let expected = answers::read(year, *day, part);
+
+            // parts that are neither implemented nor known are not worth reporting.
+            if expected.is_none() && !report.is_some_and(PartReport::is_success) {
+                continue;
+            }
+
+            let actual = report.and_then(|x| x.answer.as_deref());
+
+            match answers::check(expected.as_deref(), actual) {
+                Verdict::Pass => {
+                    passed += 1;
+                    println!("Day {day} Part {part}: {ANSI_GREEN}PASS{ANSI_RESET}");
+                }
+                Verdict::Fail { expected } => {
+                    failed += 1;
+                    let actual = match (actual, report) {
+                        (Some(actual), _) => actual,
+                        (None, Some(_)) => "no answer",
+                        (None, None) => "no report",
+                    };
+                    println!(
+                        "Day {day} Part {part}: {ANSI_RED}FAIL{ANSI_RESET} (expected {expected}, got {actual})"
+                    );
+                }
+                Verdict::Missing => {
+                    missing += 1;
+                    let actual = actual.unwrap_or_default();
+                    if record {
+                        match answers::store(year, *day, part, actual) {
+                            Ok(()) => {
+                                println!("Day {day} Part {part}: MISSING (recorded {actual})")
+                            }
+                            Err(e) => {
+                                eprintln!("Day {day} Part {part}: failed to record answer: {e}")
+                            }
+                        }
+                    } else {
+                        println!("Day {day} Part {part}: MISSING (got {actual})");
+                    }
+                }
+            }
+        }
+    }
+
+    println!("{passed} passed, {failed} failed, {missing} missing.");
+
+    if missing > 0 && !record {
+        println!(
+            "Answers are stored when a submission is accepted. Append --record to store the current answers of missing parts, once you know they are correct."
+        );
+    }
+
+    if failed > 0 {
+        process::exit(1);
+    }
+}
+
+/// Run a day against every input in `dir` and check its answers against the expected answers stored next to the inputs.
+/// With `record`, answers of parts that have no expected answer yet are stored.
+pub fn handle_inputs_dir(year: Option<Year>, day: Day, dir: &Path, is_release: bool, record: bool) {
+    let runs = multi_input::run(year, day, dir, is_release, false, &BenchConfig::default())
+        .unwrap_or_else(|e| {
+            eprintln!("{e}");
+            process::exit(1);
+        });
+
+    print!("{}", multi_input::format_table(&runs));
+
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+
+    for run in &runs {
+        let mut expected = run.expected.clone();
+
+        for part in [1, 2] {
+            match run.verdict(part) {
+                Some(Verdict::Pass) => passed += 1,
+                Some(Verdict::Fail { .. }) => failed += 1,
+                Some(Verdict::Missing) => {
+                    missing += 1;
+                    expected[usize::from(part - 1)] = run.answer(part).map(str::to_string);
+                }
+                None => {}
+            }
+        }
+
+        if record && expected != run.expected {
+            match multi_input::store_expected(&run.path, &expected) {
+                Ok(()) => println!(
+                    "Recorded answers of {} to \"{}\".",
+                    run.name(),
+                    multi_input::answers_path(&run.path).display()
+                ),
+                Err(e) => eprintln!("{}: failed to record answers: {e}", run.name()),
+            }
+        }
+    }
+
+    println!("\n{passed} passed, {failed} failed, {missing} missing.");
+
+    if failed > 0 || runs.iter().any(|x| x.error.is_some()) {
+        process::exit(1);
+    }
+}
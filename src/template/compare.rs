@@ -0,0 +1,152 @@
+/// Compares fresh benchmark results against the timings stored in `data/timings.json`.
+use std::time::Duration;
+
+use crate::template::timings::Timings;
+use crate::template::{Day, ANSI_BOLD, ANSI_GREEN, ANSI_RED, ANSI_RESET};
+
+/// The change of a single part's mean execution time.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartComparison {
+    pub day: Day,
+    pub part: u8,
+    pub stored_nanos: f64,
+    pub new_nanos: f64,
+}
+
+impl PartComparison {
+    /// Relative change in percent, positive values are slowdowns.
+    pub fn change_percent(&self) -> f64 {
+        (self.new_nanos - self.stored_nanos) / self.stored_nanos * 100.0
+    }
+
+    pub fn is_regression(&self, threshold_percent: f64) -> bool {
+        self.change_percent() > threshold_percent
+    }
+}
+
+/// Pair up every part that was benched in `new` with its stored counterpart.
+/// Parts without a stored timing are skipped.
+pub fn compare(stored: &Timings, new: &Timings) -> Vec<PartComparison> {
+    new.data
+        .iter()
+        .flat_map(|timing| {
+            let stored_timing = stored.get_day(timing.day);
+            [1, 2].into_iter().filter_map(move |part| {
+                Some(PartComparison {
+                    day: timing.day,
+                    part,
+                    stored_nanos: stored_timing?.part_nanos(part).filter(|x| *x > 0.0)?,
+                    new_nanos: timing.part_nanos(part)?,
+                })
+            })
+        })
+        .collect()
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn format_nanos(nanos: f64) -> String {
+    format!("{:.1?}", Duration::from_nanos(nanos as u64))
+}
+
+/// Print a colour-coded comparison table. Returns `true` if any part regressed beyond `threshold_percent`.
+pub fn print_comparisons(comparisons: &[PartComparison], threshold_percent: f64) -> bool {
+    println!();
+    println!(
+        "{ANSI_BOLD}Comparison with stored timings{ANSI_RESET} (threshold: {threshold_percent}%)"
+    );
+    println!("------");
+
+    if comparisons.is_empty() {
+        println!("No stored timings to compare against.");
+        return false;
+    }
+
+    for comparison in comparisons {
+        let change = comparison.change_percent();
+        let colour = if comparison.is_regression(threshold_percent) {
+            ANSI_RED
+        } else if change < -threshold_percent {
+            ANSI_GREEN
+        } else {
+            ""
+        };
+
+        println!(
+            "Day {} Part {}: {} → {} ({colour}{change:+.1}%{ANSI_RESET})",
+            comparison.day,
+            comparison.part,
+            format_nanos(comparison.stored_nanos),
+            format_nanos(comparison.new_nanos),
+        );
+    }
+
+    let regressions = comparisons
+        .iter()
+        .filter(|c| c.is_regression(threshold_percent))
+        .count();
+
+    if regressions > 0 {
+        println!("{ANSI_RED}{regressions} part(s) regressed by more than {threshold_percent}%.{ANSI_RESET}");
+    }
+
+    regressions > 0
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::compare;
+    use crate::{
+        day,
+        template::timings::{Timing, Timings},
+    };
+
+    fn get_timing(day: crate::template::Day, part_1: Option<&str>, part_2: Option<&str>) -> Timing {
+        Timing {
+            day,
+            part_1: part_1.map(Into::into),
+            part_2: part_2.map(Into::into),
+            part_1_stats: None,
+            part_2_stats: None,
+            part_1_allocations: None,
+            part_2_allocations: None,
+            failed_parts: vec![],
+            total_nanos: 0_f64,
+        }
+    }
+
+    #[test]
+    fn compares_matching_parts() {
+        let stored = Timings {
+            history: vec![],
+            data: vec![
+                get_timing(day!(1), Some("10.0ms"), Some("1.0ms")),
+                get_timing(day!(2), Some("1.0ms"), None),
+            ],
+        };
+        let new = Timings {
+            history: vec![],
+            data: vec![
+                get_timing(day!(1), Some("12.0ms"), Some("0.5ms")),
+                get_timing(day!(2), Some("1.0ms"), Some("3.0ms")),
+                get_timing(day!(3), Some("1.0ms"), None),
+            ],
+        };
+
+        let comparisons = compare(&stored, &new);
+        assert_eq!(comparisons.len(), 3);
+
+        assert_eq!(comparisons[0].day, day!(1));
+        assert_eq!(comparisons[0].part, 1);
+        assert!((comparisons[0].change_percent() - 20.0).abs() < 1e-9);
+        assert!(comparisons[0].is_regression(10.0));
+        assert!(!comparisons[0].is_regression(25.0));
+
+        assert!((comparisons[1].change_percent() + 50.0).abs() < 1e-9);
+        assert!(!comparisons[1].is_regression(0.0));
+
+        assert_eq!(comparisons[2].day, day!(2));
+        assert!((comparisons[2].change_percent()).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,219 @@
+/// Extracts example inputs from the Markdown puzzle descriptions in `data/puzzles`.
+use crate::template::Day;
+
+/// A fenced code block of a puzzle description.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CodeBlock {
+    /// The part of the puzzle the block appears in.
+    pub part: u8,
+    pub content: String,
+    /// Whether the text leading up to the block announces an example, e.g. "For example:".
+    pub is_example: bool,
+}
+
+impl CodeBlock {
+    pub fn line_count(&self) -> usize {
+        self.content.lines().count()
+    }
+}
+
+/// All fenced code blocks of a puzzle description, in order.
+pub fn code_blocks(markdown: &str) -> Vec<CodeBlock> {
+    let mut blocks = vec![];
+    let mut part = 1;
+    let mut last_text = "";
+    let mut lines = markdown.lines();
+
+    while let Some(line) = lines.next() {
+        if line.starts_with("```") {
+            let content: Vec<&str> = lines
+                .by_ref()
+                .take_while(|line| !line.starts_with("```"))
+                .collect();
+
+            blocks.push(CodeBlock {
+                part,
+                content: content.join("\n"),
+                is_example: last_text.to_lowercase().contains("example"),
+            });
+            last_text = "";
+        } else if line.starts_with('#') && line.contains("Part Two") {
+            part = 2;
+            last_text = "";
+        } else if !line.trim().is_empty() {
+            last_text = line;
+        }
+    }
+
+    blocks
+}
+
+/// Pick the example inputs of a puzzle: per part, the first block that is announced as an example,
+/// falling back to the longest block. Blocks that repeat an earlier pick are not considered.
+pub fn select(blocks: &[CodeBlock]) -> Vec<&CodeBlock> {
+    let mut selected: Vec<&CodeBlock> = vec![];
+
+    for part in [1, 2] {
+        let candidates: Vec<&CodeBlock> = blocks
+            .iter()
+            .filter(|x| x.part == part && !x.content.trim().is_empty())
+            .filter(|x| !selected.iter().any(|y| y.content == x.content))
+            .collect();
+
+        let pick = candidates
+            .iter()
+            .find(|x| x.is_example)
+            .or_else(|| candidates.iter().max_by_key(|x| x.line_count()));
+
+        if let Some(pick) = pick {
+            selected.push(pick);
+        }
+    }
+
+    selected
+}
+
+/// The example answer of each part: the last emphasized code span in the part, e.g. *`11`* or `*11*`.
+/// The answer of part two is [`None`] until it is unlocked.
+pub fn example_answers(markdown: &str) -> [Option<String>; 2] {
+    let mut answers = [None, None];
+    let mut part = 1;
+    let mut in_block = false;
+
+    for line in markdown.lines() {
+        if line.starts_with("```") {
+            in_block = !in_block;
+        } else if in_block {
+            continue;
+        } else if line.starts_with('#') && line.contains("Part Two") {
+            part = 2;
+        } else if let Some(answer) = emphasized_code(line).last() {
+            answers[part - 1] = Some((*answer).to_string());
+        }
+    }
+
+    answers
+}
+
+/// Code spans of a line that are emphasized, either around or within the backticks.
+fn emphasized_code(line: &str) -> Vec<&str> {
+    let segments: Vec<&str> = line.split('`').collect();
+
+    // every odd segment is a code span, as long as it is followed by a closing backtick.
+    (1..segments.len().saturating_sub(1))
+        .step_by(2)
+        .filter_map(|i| {
+            let code = segments[i];
+            let inner = code.strip_prefix('*').and_then(|x| x.strip_suffix('*'));
+            let is_wrapped = segments[i - 1].ends_with('*') && segments[i + 1].starts_with('*');
+            inner.or(is_wrapped.then_some(code))
+        })
+        .filter(|x| !x.trim().is_empty())
+        .collect()
+}
+
+/// The file name of the `index`-th (0-based) example of a day, matching `read_file_part`: `01.txt`, `01-2.txt`, ...
+pub fn file_name(day: Day, index: usize) -> String {
+    match index {
+        0 => format!("{day}.txt"),
+        i => format!("{day}-{}.txt", i + 1),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{code_blocks, example_answers, file_name, select};
+    use crate::day;
+
+    const PUZZLE: &str = "## --- Day 1: Historian Hysteria ---
+
+The lists look like `3   4`:
+
+```
+xyz
+```
+
+For example:
+
+```
+3   4
+4   3
+```
+
+What is the total?
+
+## --- Part Two ---
+
+Consider the example again, it contains a `x`:
+
+```
+3   4
+4   3
+```
+
+A new example:
+
+```
+1
+2
+```
+";
+
+    #[test]
+    fn extracts_code_blocks() {
+        let blocks = code_blocks(PUZZLE);
+        assert_eq!(blocks.len(), 4);
+        assert_eq!(blocks[0].content, "xyz");
+        assert!(!blocks[0].is_example);
+        assert_eq!(blocks[1].content, "3   4\n4   3");
+        assert!(blocks[1].is_example);
+        assert_eq!(blocks[2].part, 2);
+        assert_eq!(blocks[3].line_count(), 2);
+    }
+
+    #[test]
+    fn selects_examples() {
+        let blocks = code_blocks(PUZZLE);
+        let selected: Vec<&str> = select(&blocks)
+            .into_iter()
+            .map(|x| x.content.as_str())
+            .collect();
+        assert_eq!(selected, vec!["3   4\n4   3", "1\n2"]);
+
+        let blocks = code_blocks("```\na\n```\n\n```\nb\nc\n```\n");
+        assert_eq!(select(&blocks)[0].content, "b\nc");
+    }
+
+    #[test]
+    fn names_files_like_read_file_part() {
+        assert_eq!(file_name(day!(1), 0), "01.txt");
+        assert_eq!(file_name(day!(1), 1), "01-2.txt");
+    }
+
+    #[test]
+    fn detects_example_answers() {
+        let puzzle = "## --- Day 1 ---
+
+In the example, `3` and `*4*` add up to *`7`*.
+
+```
+*`1`*
+```
+
+In total, the distance is *`11`*.
+
+## --- Part Two ---
+
+The similarity is `*31*`.
+";
+        assert_eq!(
+            example_answers(puzzle),
+            [Some("11".to_string()), Some("31".to_string())]
+        );
+
+        let (part_one, _) = puzzle.split_once("## --- Part Two").unwrap();
+        assert_eq!(example_answers(part_one), [Some("11".to_string()), None]);
+    }
+}
@@ -0,0 +1,217 @@
+/// Renders CPU profiles recorded with `perf` as flamegraph SVGs, see `solve --profile cpu`.
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// Stacks and how often they were sampled, keyed by their frames from the root to the leaf, joined with `;`.
+pub type FoldedStacks = BTreeMap<String, u64>;
+
+/// Fold the samples printed by `perf script` into stacks.
+///
+/// Every sample is a header line, followed by one indented line per frame (leaf first) and a blank line.
+/// Frames look like `55d5c0a1b2c3 day_01::part_one+0x23 (/path/to/01)`.
+pub fn fold_perf_script(script: &str) -> FoldedStacks {
+    let mut folded = FoldedStacks::new();
+    let mut frames: Vec<String> = vec![];
+
+    let mut flush = |frames: &mut Vec<String>| {
+        if !frames.is_empty() {
+            frames.reverse();
+            *folded.entry(frames.join(";")).or_default() += 1;
+            frames.clear();
+        }
+    };
+
+    for line in script.lines() {
+        if line.trim().is_empty() {
+            flush(&mut frames);
+        } else if line.starts_with(char::is_whitespace) {
+            frames.push(frame_name(line.trim()));
+        } else {
+            // the header of the next sample.
+            flush(&mut frames);
+        }
+    }
+    flush(&mut frames);
+
+    folded
+}
+
+/// The function of a frame line, without its address, offset and module.
+fn frame_name(line: &str) -> String {
+    let without_address = line.split_once(' ').map_or(line, |(_, rest)| rest);
+    let without_module = without_address
+        .rsplit_once(" (")
+        .map_or(without_address, |(name, _)| name);
+    let name = without_module
+        .rsplit_once("+0x")
+        .map_or(without_module, |(name, _)| name);
+    // `;` separates frames in folded stacks.
+    name.trim().replace(';', ":")
+}
+
+/* -------------------------------------------------------------------------- */
+
+const WIDTH: f64 = 1200.0;
+const FRAME_HEIGHT: f64 = 16.0;
+const PADDING: f64 = 10.0;
+const CHAR_WIDTH: f64 = 7.0;
+
+#[derive(Default)]
+struct Node {
+    samples: u64,
+    children: BTreeMap<String, Node>,
+}
+
+/// Render folded stacks as a flamegraph SVG, with the root at the bottom and hover titles for every frame.
+pub fn render_svg(folded: &FoldedStacks, title: &str) -> String {
+    let mut root = Node::default();
+    for (stack, samples) in folded {
+        root.samples += samples;
+        let mut node = &mut root;
+        for frame in stack.split(';') {
+            node = node.children.entry(frame.to_string()).or_default();
+            node.samples += samples;
+        }
+    }
+
+    let depth = max_depth(&root);
+    #[allow(clippy::cast_precision_loss)]
+    let height = (depth + 1) as f64 * FRAME_HEIGHT + 3.0 * PADDING + FRAME_HEIGHT;
+
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{WIDTH}" height="{height}" viewBox="0 0 {WIDTH} {height}" font-family="monospace" font-size="12">"#
+    );
+    let _ = writeln!(
+        svg,
+        r##"<rect width="100%" height="100%" fill="#f8f8f8"/><text x="{}" y="{}" text-anchor="middle" font-size="16">{}</text>"##,
+        WIDTH / 2.0,
+        PADDING + FRAME_HEIGHT / 2.0 + 4.0,
+        escape(title)
+    );
+
+    if root.samples > 0 {
+        let bottom = height - PADDING - FRAME_HEIGHT;
+        let mut frames = String::new();
+        render_node(&mut frames, "all", &root, root.samples, PADDING, bottom);
+        svg.push_str(&frames);
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn max_depth(node: &Node) -> usize {
+    node.children
+        .values()
+        .map(|x| 1 + max_depth(x))
+        .max()
+        .unwrap_or(0)
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn render_node(out: &mut String, name: &str, node: &Node, total: u64, x: f64, y: f64) {
+    let width = node.samples as f64 / total as f64 * (WIDTH - 2.0 * PADDING);
+    // frames narrower than a pixel can't be seen, and neither can their children.
+    if width < 1.0 {
+        return;
+    }
+
+    let percent = node.samples as f64 / total as f64 * 100.0;
+    let max_chars = (width / CHAR_WIDTH).floor() as usize;
+    let label = if max_chars < 3 {
+        String::new()
+    } else if name.chars().count() <= max_chars {
+        name.to_string()
+    } else {
+        let truncated: String = name.chars().take(max_chars - 2).collect();
+        format!("{truncated}..")
+    };
+
+    let _ = writeln!(
+        out,
+        r#"<g><title>{} ({} samples, {percent:.2}%)</title><rect x="{x:.1}" y="{y:.1}" width="{width:.1}" height="{}" fill="{}" rx="2"/><text x="{:.1}" y="{:.1}">{}</text></g>"#,
+        escape(name),
+        node.samples,
+        FRAME_HEIGHT - 1.0,
+        color(name),
+        x + 3.0,
+        y + FRAME_HEIGHT - 4.5,
+        escape(&label)
+    );
+
+    let mut child_x = x;
+    for (child_name, child) in &node.children {
+        render_node(out, child_name, child, total, child_x, y - FRAME_HEIGHT);
+        child_x += child.samples as f64 / total as f64 * (WIDTH - 2.0 * PADDING);
+    }
+}
+
+/// A warm color that is stable per function name, so that the same function looks the same across profiles.
+fn color(name: &str) -> String {
+    // FNV-1a
+    let hash = name.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, b| {
+        (hash ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3)
+    });
+    let r = 205 + hash % 50;
+    let g = (hash >> 8) % 230;
+    let b = (hash >> 16) % 55;
+    format!("rgb({r},{g},{b})")
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{fold_perf_script, render_svg, FoldedStacks};
+
+    const SCRIPT: &str = "01 4242 100.000001:     250000 cpu-clock:u:
+\t    55d5c0a1b2c3 core::str::<impl str>::parse+0x13 (/root/target/profiling/01)
+\t    55d5c0a1b2d4 _01::part_one+0x23 (/root/target/profiling/01)
+\t    55d5c0a1b2e5 main+0x5 (/root/target/profiling/01)
+
+01 4242 100.000002:     250000 cpu-clock:u:
+\t    55d5c0a1b2d4 _01::part_one+0x42 (/root/target/profiling/01)
+\t    55d5c0a1b2e5 main+0x5 (/root/target/profiling/01)
+
+01 4242 100.000003:     250000 cpu-clock:u:
+\t    55d5c0a1b2d4 _01::part_one+0x42 (/root/target/profiling/01)
+\t    55d5c0a1b2e5 main+0x5 (/root/target/profiling/01)
+";
+
+    #[test]
+    fn folds_perf_samples() {
+        let folded = fold_perf_script(SCRIPT);
+        assert_eq!(
+            folded.into_iter().collect::<Vec<_>>(),
+            [
+                ("main;_01::part_one".to_string(), 2),
+                (
+                    "main;_01::part_one;core::str::<impl str>::parse".to_string(),
+                    1
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn renders_svgs() {
+        let svg = render_svg(&fold_perf_script(SCRIPT), "Day 01");
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains("<title>all (3 samples, 100.00%)</title>"));
+        assert!(
+            svg.contains("<title>core::str::&lt;impl str&gt;::parse (1 samples, 33.33%)</title>")
+        );
+        assert!(svg.trim_end().ends_with("</svg>"));
+
+        assert!(!render_svg(&FoldedStacks::new(), "Day 01").contains("<g>"));
+    }
+}
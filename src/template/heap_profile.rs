@@ -0,0 +1,205 @@
+/// Summarizes the `dhat-heap.json` files written by `solve --dhat`, so that the profile can be read in the terminal.
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use tinyjson::JsonValue;
+
+/// Number of allocation sites listed in the summary.
+const TOP_SITES: usize = 5;
+
+/// Frames of the allocator and the standard library, which are skipped to find the code that allocated.
+const INTERNAL_PREFIXES: [&str; 9] = [
+    "[root]", "alloc::", "<alloc::", "core::", "<core::", "std::", "<std::", "dhat::", "<dhat::",
+];
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct HeapSummary {
+    pub total: Usage,
+    /// At the time of the largest heap size.
+    pub peak: Usage,
+    /// At the end of the profile, i.e. memory that was not freed.
+    pub end: Usage,
+    /// The sites that allocated the most bytes in total, in descending order.
+    pub sites: Vec<AllocationSite>,
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Usage {
+    pub bytes: u64,
+    pub blocks: u64,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct AllocationSite {
+    pub total: Usage,
+    /// The first frame outside of the allocator and the standard library, e.g. `day_01::parse (src/bin/01.rs:12:5)`.
+    pub frame: String,
+}
+
+impl FromStr for HeapSummary {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let json = JsonValue::from_str(s).or(Err("not a valid JSON file."))?;
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        let frames: Vec<&str> = json
+            .get("ftbl")
+            .and_then(|x| x.get::<Vec<JsonValue>>())
+            .ok_or("expected `ftbl` to be an array.")?
+            .iter()
+            .map(|x| x.get::<String>().map_or("", String::as_str))
+            .collect();
+
+        let points = json
+            .get("pps")
+            .and_then(|x| x.get::<Vec<JsonValue>>())
+            .ok_or("expected `pps` to be an array.")?;
+
+        let mut summary = HeapSummary {
+            total: Usage::default(),
+            peak: Usage::default(),
+            end: Usage::default(),
+            sites: vec![],
+        };
+
+        for point in points {
+            let point = point
+                .get::<HashMap<String, JsonValue>>()
+                .ok_or("expected program point to be an object.")?;
+            let number = |key: &str| {
+                point
+                    .get(key)
+                    .and_then(|x| x.get::<f64>())
+                    .map(|x| *x as u64)
+                    .ok_or(format!("expected program point to have a number `{key}`."))
+            };
+
+            let total = Usage {
+                bytes: number("tb")?,
+                blocks: number("tbk")?,
+            };
+            summary.total.bytes += total.bytes;
+            summary.total.blocks += total.blocks;
+            summary.peak.bytes += number("gb")?;
+            summary.peak.blocks += number("gbk")?;
+            summary.end.bytes += number("eb")?;
+            summary.end.blocks += number("ebk")?;
+
+            let stack: Vec<&str> = point
+                .get("fs")
+                .and_then(|x| x.get::<Vec<JsonValue>>())
+                .ok_or("expected program point to have frames `fs`.")?
+                .iter()
+                .filter_map(|x| x.get::<f64>())
+                .filter_map(|x| frames.get(*x as usize).copied())
+                .map(strip_address)
+                .collect();
+
+            let frame = stack
+                .iter()
+                .find(|x| !INTERNAL_PREFIXES.iter().any(|p| x.starts_with(p)))
+                .or(stack.first())
+                .map_or_else(|| "[unknown]".to_string(), ToString::to_string);
+
+            summary.sites.push(AllocationSite { total, frame });
+        }
+
+        summary.sites.sort_by_key(|x| Reverse(x.total.bytes));
+        summary.sites.truncate(TOP_SITES);
+
+        Ok(summary)
+    }
+}
+
+/// A frame without its address, e.g. `main (src/main.rs:1:1)` for `0x1234: main (src/main.rs:1:1)`.
+fn strip_address(frame: &str) -> &str {
+    match frame.split_once(": ") {
+        Some((address, rest)) if address.starts_with("0x") => rest,
+        _ => frame,
+    }
+}
+
+impl Display for Usage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} bytes in {} blocks", self.bytes, self.blocks)
+    }
+}
+
+impl Display for HeapSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Total:     {}", self.total)?;
+        writeln!(f, "At t-gmax: {}", self.peak)?;
+        writeln!(f, "At t-end:  {}", self.end)?;
+
+        if !self.sites.is_empty() {
+            writeln!(f, "\nTop allocation sites:")?;
+            for site in &self.sites {
+                writeln!(f, "{:>12}  {}", site.total.to_string(), site.frame)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{AllocationSite, HeapSummary, Usage};
+
+    const PROFILE: &str = r#"{
+"dhatFileVersion": 2, "mode": "rust-heap", "verb": "Allocated", "cmd": "target/dhat/01", "pid": 1,
+"pps": [
+  {"tb": 32, "tbk": 2, "tl": 10, "mb": 32, "mbk": 2, "gb": 16, "gbk": 1, "eb": 0, "ebk": 0, "fs": [1, 2, 3]},
+  {"tb": 200, "tbk": 1, "tl": 10, "mb": 200, "mbk": 1, "gb": 200, "gbk": 1, "eb": 8, "ebk": 1, "fs": [1, 4]}
+],
+"ftbl": [
+  "[root]",
+  "0x55a1: alloc::alloc::alloc (alloc.rs:98:9)",
+  "0x55a2: <alloc::vec::Vec<T>>::push (vec.rs:1:1)",
+  "0x55a3: _01::read_list (src/bin/01.rs:12:5)",
+  "0x55a4: _01::part_two (src/bin/01.rs:40:9)"
+]}"#;
+
+    #[test]
+    fn summarizes_profiles() {
+        let summary: HeapSummary = PROFILE.parse().unwrap();
+        assert_eq!(
+            summary.total,
+            Usage {
+                bytes: 232,
+                blocks: 3
+            }
+        );
+        assert_eq!(summary.peak.bytes, 216);
+        assert_eq!(summary.end.blocks, 1);
+        assert_eq!(
+            summary.sites[0],
+            AllocationSite {
+                total: Usage {
+                    bytes: 200,
+                    blocks: 1
+                },
+                frame: "_01::part_two (src/bin/01.rs:40:9)".into()
+            }
+        );
+        assert_eq!(
+            summary.sites[1].frame,
+            "_01::read_list (src/bin/01.rs:12:5)"
+        );
+        assert!(summary
+            .to_string()
+            .starts_with("Total:     232 bytes in 3 blocks\n"));
+    }
+
+    #[test]
+    fn rejects_other_files() {
+        assert!("{}".parse::<HeapSummary>().is_err());
+        assert!("not json".parse::<HeapSummary>().is_err());
+    }
+}
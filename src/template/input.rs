@@ -0,0 +1,335 @@
+/// Loads puzzle inputs and examples from `data`, checking that they look like puzzle data.
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::{env, fs, io, io::Read};
+
+use crate::template::{year_file_path, Day, Year};
+
+/// Contents of files that have been read already, keyed by path. Tests read the same examples over and over.
+static CACHE: OnceLock<Mutex<HashMap<PathBuf, String>>> = OnceLock::new();
+
+/// An error which can be returned when loading an input or example file.
+#[derive(Debug)]
+pub struct InputError {
+    pub path: PathBuf,
+    pub kind: InputErrorKind,
+    folder: String,
+    day: Day,
+}
+
+#[derive(Debug)]
+pub enum InputErrorKind {
+    Missing,
+    Unreadable(io::Error),
+    /// The file is empty or only contains whitespace, like the placeholder created by `scaffold`.
+    Empty,
+    /// The file contains a web page instead of the input, e.g. an error page of the website.
+    Html,
+    /// The file contains the response of the website to a request that was not logged in.
+    NotLoggedIn,
+    /// The file contains the response of the website to a request for a puzzle that is not unlocked yet.
+    Locked,
+}
+
+/// Read the file at `path`, in `folder` of day `day`, with line endings normalized to `\n`
+/// and trailing whitespace at the end of the file reduced to one line break.
+/// Empty examples are read as empty strings, as `scaffold` creates them before the example is known.
+pub fn load(path: &Path, folder: &str, day: Day) -> Result<String, InputError> {
+    let cache = CACHE.get_or_init(Default::default);
+
+    if let Some(content) = cache.lock().unwrap().get(path) {
+        return Ok(content.clone());
+    }
+
+    let error = |kind| InputError {
+        path: path.to_path_buf(),
+        kind,
+        folder: folder.to_string(),
+        day,
+    };
+
+    let content = fs::read_to_string(path).map_err(|e| match e.kind() {
+        io::ErrorKind::NotFound => error(InputErrorKind::Missing),
+        _ => error(InputErrorKind::Unreadable(e)),
+    })?;
+
+    let content = check(&content, folder == "examples").map_err(error)?;
+
+    cache
+        .lock()
+        .unwrap()
+        .insert(path.to_path_buf(), content.clone());
+    Ok(content)
+}
+
+/// Where a solution binary reads its input from.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum InputSource {
+    /// The puzzle input in `data/inputs`.
+    #[default]
+    Puzzle,
+    /// The `n`-th example in `data/examples`, e.g. `01-2.txt` for `n = 2`.
+    Example(u8),
+    /// Any file, e.g. the input of someone else.
+    File(PathBuf),
+    Stdin,
+}
+
+impl InputSource {
+    /// Reads the source from the arguments of a solution binary:
+    /// `--input <path>`, `--input -` or `-` for stdin, and `--example [n]`.
+    pub fn from_args() -> Result<Self, String> {
+        let args: Vec<String> = env::args().skip(1).collect();
+        let index_of = |name: &str| args.iter().position(|x| x == name);
+
+        let source = match (index_of("--input"), index_of("--example")) {
+            (Some(_), Some(_)) => return Err("use either --input or --example, not both".into()),
+            (Some(i), None) => match args.get(i + 1).map(String::as_str) {
+                Some("-") => Self::Stdin,
+                Some(path) => Self::File(PathBuf::from(path)),
+                None => return Err("missing path after --input".into()),
+            },
+            (None, Some(i)) => match args.get(i + 1).and_then(|x| x.parse().ok()) {
+                Some(0) => return Err("examples are numbered from 1".into()),
+                Some(n) => Self::Example(n),
+                None => Self::Example(1),
+            },
+            (None, None) if args.iter().any(|x| x == "-") => Self::Stdin,
+            (None, None) => Self::Puzzle,
+        };
+
+        Ok(source)
+    }
+
+    /// Arguments that pass this source to a solution binary.
+    pub fn to_args(&self) -> Vec<String> {
+        match self {
+            Self::Puzzle => vec![],
+            Self::Example(n) => vec!["--example".into(), n.to_string()],
+            Self::File(path) => vec!["--input".into(), path.display().to_string()],
+            Self::Stdin => vec!["--input".into(), "-".into()],
+        }
+    }
+
+    pub fn read(&self, year: Option<Year>, day: Day) -> Result<String, InputError> {
+        match self {
+            Self::Puzzle => load(
+                &year_file_path("inputs", year, &format!("{day}.txt")),
+                "inputs",
+                day,
+            ),
+            Self::Example(n) => {
+                let file_name = match n {
+                    1 => format!("{day}.txt"),
+                    n => format!("{day}-{n}.txt"),
+                };
+                load(
+                    &year_file_path("examples", year, &file_name),
+                    "examples",
+                    day,
+                )
+            }
+            Self::File(path) => load(path, "", day),
+            Self::Stdin => {
+                let error = |kind| InputError {
+                    path: PathBuf::from("<stdin>"),
+                    kind,
+                    folder: "stdin".into(),
+                    day,
+                };
+
+                let mut content = String::new();
+                io::stdin()
+                    .read_to_string(&mut content)
+                    .map_err(|e| error(InputErrorKind::Unreadable(e)))?;
+                check(&content, false).map_err(error)
+            }
+        }
+    }
+}
+
+impl Display for InputSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Puzzle => write!(f, "the puzzle input"),
+            Self::Example(n) => write!(f, "example {n}"),
+            Self::File(path) => write!(f, "\"{}\"", path.display()),
+            Self::Stdin => write!(f, "stdin"),
+        }
+    }
+}
+
+/// The normalized content of a file, or the reason why it is not puzzle data.
+fn check(content: &str, allow_empty: bool) -> Result<String, InputErrorKind> {
+    let trimmed = content.trim();
+
+    if trimmed.is_empty() {
+        return if allow_empty {
+            Ok(String::new())
+        } else {
+            Err(InputErrorKind::Empty)
+        };
+    }
+
+    let start = trimmed[..trimmed.len().min(100)].to_ascii_lowercase();
+    if start.starts_with("<!doctype html") || start.starts_with("<html") {
+        return Err(InputErrorKind::Html);
+    }
+    if trimmed.starts_with("Puzzle inputs differ by user") {
+        return Err(InputErrorKind::NotLoggedIn);
+    }
+    if trimmed.starts_with("Please don't repeatedly request this endpoint before it unlocks") {
+        return Err(InputErrorKind::Locked);
+    }
+
+    let mut normalized = content.replace("\r\n", "\n");
+    normalized.truncate(normalized.trim_end().len());
+    normalized.push('\n');
+    Ok(normalized)
+}
+
+impl InputError {
+    /// What to do about the error.
+    fn hint(&self) -> String {
+        let day = self.day;
+        let download = format!("run `cargo download {day}` to fetch it");
+
+        match (&self.kind, self.folder.as_str()) {
+            (InputErrorKind::Unreadable(_), "stdin") => "check the command that is piped in".into(),
+            (InputErrorKind::Unreadable(_), _) => "check the permissions of the file".into(),
+            (InputErrorKind::NotLoggedIn, _) => {
+                format!("configure your session cookie, then {download}")
+            }
+            (InputErrorKind::Locked, _) => {
+                format!("wait until the puzzle unlocks, then {download}")
+            }
+            (_, "examples") => {
+                format!("paste the example of the puzzle into it, or run `cargo examples {day}`")
+            }
+            (_, "inputs") => download,
+            (_, "stdin") => "check the command that is piped in".into(),
+            (InputErrorKind::Missing, _) => "check the path".into(),
+            _ => "check the content of the file".into(),
+        }
+    }
+}
+
+impl Error for InputError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self.kind {
+            InputErrorKind::Unreadable(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let path = self.path.display();
+        match &self.kind {
+            InputErrorKind::Missing => write!(f, "\"{path}\" does not exist")?,
+            InputErrorKind::Unreadable(e) => write!(f, "could not read \"{path}\": {e}")?,
+            InputErrorKind::Empty => write!(f, "\"{path}\" is empty")?,
+            InputErrorKind::Html => write!(f, "\"{path}\" contains a web page, not puzzle data")?,
+            InputErrorKind::NotLoggedIn => write!(
+                f,
+                "\"{path}\" contains the website's response to a request without a session cookie"
+            )?,
+            InputErrorKind::Locked => write!(
+                f,
+                "\"{path}\" contains the website's response to a request for a locked puzzle"
+            )?,
+        }
+        write!(f, ", {}.", self.hint())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::PathBuf;
+    use std::{env, fs, process};
+
+    use super::{check, load, InputError, InputErrorKind, InputSource};
+    use crate::day;
+
+    #[test]
+    fn normalizes_content() {
+        assert_eq!(check("1\r\n2\r\n\r\n", false).unwrap(), "1\n2\n");
+        assert_eq!(check("  x\n  y", false).unwrap(), "  x\n  y\n");
+        assert_eq!(check(" \n", true).unwrap(), "");
+    }
+
+    #[test]
+    fn detects_bad_content() {
+        assert!(matches!(check("", false), Err(InputErrorKind::Empty)));
+        assert!(matches!(check(" \n\n", false), Err(InputErrorKind::Empty)));
+        assert!(matches!(
+            check("<!DOCTYPE html>\n<html lang=\"en-us\">", true),
+            Err(InputErrorKind::Html)
+        ));
+        assert!(matches!(
+            check(
+                "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n",
+                false
+            ),
+            Err(InputErrorKind::NotLoggedIn)
+        ));
+    }
+
+    #[test]
+    fn suggests_fixes() {
+        let error = |kind, folder: &str| InputError {
+            path: PathBuf::from("data/inputs/05.txt"),
+            kind,
+            folder: folder.to_string(),
+            day: day!(5),
+        };
+
+        assert_eq!(
+            error(InputErrorKind::Empty, "inputs").to_string(),
+            "\"data/inputs/05.txt\" is empty, run `cargo download 05` to fetch it."
+        );
+        assert!(error(InputErrorKind::Missing, "examples")
+            .to_string()
+            .ends_with("or run `cargo examples 05`."));
+    }
+
+    #[test]
+    fn loads_empty_examples_only() {
+        let path =
+            |folder: &str| env::temp_dir().join(format!("aoc-{folder}-{}.txt", process::id()));
+        let load_empty = |folder: &str| {
+            fs::write(path(folder), "").unwrap();
+            let result = load(&path(folder), folder, day!(25));
+            fs::remove_file(path(folder)).unwrap();
+            result
+        };
+        let example = load_empty("examples");
+        let input = load_empty("inputs");
+
+        assert_eq!(example.unwrap(), "");
+        assert!(matches!(
+            input,
+            Err(InputError {
+                kind: InputErrorKind::Empty,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn forwards_sources() {
+        assert!(InputSource::Puzzle.to_args().is_empty());
+        assert_eq!(InputSource::Example(2).to_args(), ["--example", "2"]);
+        assert_eq!(InputSource::Stdin.to_args(), ["--input", "-"]);
+        assert_eq!(
+            InputSource::File(PathBuf::from("../other/05.txt")).to_string(),
+            "\"../other/05.txt\""
+        );
+    }
+}
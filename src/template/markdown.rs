@@ -0,0 +1,359 @@
+/// Convert the `<article class="day-desc">` blocks of a puzzle page to Markdown.
+/// `base_url` is used to resolve relative links.
+///
+/// This is not a general-purpose converter: it handles the small subset of HTML that puzzle pages use,
+/// i.e. headings, paragraphs, emphasis, inline code, code blocks, lists and links.
+pub fn puzzle_to_markdown(html: &str, base_url: &str) -> String {
+    let articles: Vec<String> = extract_elements(html, "article")
+        .into_iter()
+        .map(|article| to_markdown(article, base_url))
+        .collect();
+
+    let mut markdown = articles.join("\n\n");
+    markdown.push('\n');
+    markdown
+}
+
+/// Extract the text content of the first `<article>` of a page, e.g. the response to a submission.
+pub fn article_text(html: &str) -> Option<String> {
+    let article = extract_elements(html, "article").into_iter().next()?;
+    let mut text = String::new();
+    for token in Tokenizer::new(article) {
+        if let Token::Text(x) = token {
+            text.push_str(&decode_entities(x));
+        }
+    }
+    Some(collapse_whitespace(&text))
+}
+
+/// Inner HTML of every `<name ...>...</name>` element, not supporting nested elements of the same name.
+fn extract_elements<'a>(html: &'a str, name: &str) -> Vec<&'a str> {
+    let open = format!("<{name}");
+    let close = format!("</{name}>");
+    let mut elements = vec![];
+    let mut rest = html;
+
+    while let Some(start) = rest.find(&open) {
+        let Some(content_start) = rest[start..].find('>').map(|i| start + i + 1) else {
+            break;
+        };
+        let Some(end) = rest[content_start..]
+            .find(&close)
+            .map(|i| content_start + i)
+        else {
+            break;
+        };
+        elements.push(&rest[content_start..end]);
+        rest = &rest[end + close.len()..];
+    }
+
+    elements
+}
+
+fn to_markdown(html: &str, base_url: &str) -> String {
+    let mut out = String::new();
+    let mut in_pre = false;
+    let mut in_code = false;
+    // emphasis can't be nested into code spans, so `<code><em>x</em></code>` is moved around it: *`x`*.
+    let mut is_emphasized_code = false;
+    let mut links: Vec<String> = vec![];
+
+    for token in Tokenizer::new(html) {
+        match token {
+            Token::Text(text) => {
+                let text = decode_entities(text);
+                if in_pre {
+                    out.push_str(&text);
+                } else {
+                    let text = collapse_inline_whitespace(&text);
+                    // whitespace between blocks is not part of the content.
+                    if out.is_empty() || out.ends_with('\n') {
+                        out.push_str(text.trim_start());
+                    } else {
+                        out.push_str(&text);
+                    }
+                }
+            }
+            Token::Open { name, attrs } => match name.as_str() {
+                "h2" => out.push_str("## "),
+                "pre" => {
+                    in_pre = true;
+                    out.push_str("```\n");
+                }
+                "code" if !in_pre => {
+                    in_code = true;
+                    out.push('`');
+                }
+                "em" if in_code && out.ends_with('`') => {
+                    out.insert(out.len() - 1, '*');
+                    is_emphasized_code = true;
+                }
+                "em" if !in_pre && !in_code => out.push('*'),
+                "li" => out.push_str("- "),
+                "a" => {
+                    links.push(resolve_link(attr(&attrs, "href"), base_url));
+                    out.push('[');
+                }
+                "br" => out.push('\n'),
+                _ => {}
+            },
+            Token::Close { name } => match name.as_str() {
+                "h2" | "p" | "ul" => end_block(&mut out, "\n\n"),
+                "pre" => {
+                    in_pre = false;
+                    end_block(&mut out, "\n```\n\n");
+                }
+                "code" if !in_pre => {
+                    in_code = false;
+                    out.push('`');
+                    if is_emphasized_code {
+                        is_emphasized_code = false;
+                        out.push('*');
+                    }
+                }
+                "em" if !in_pre && !in_code => out.push('*'),
+                "li" => end_block(&mut out, "\n"),
+                "a" => {
+                    let href = links.pop().unwrap_or_default();
+                    out.push_str(&format!("]({href})"));
+                }
+                _ => {}
+            },
+        }
+    }
+
+    out.trim().to_string()
+}
+
+/// Terminate the current block with `separator`, replacing any trailing whitespace.
+fn end_block(out: &mut String, separator: &str) {
+    out.truncate(out.trim_end().len());
+    out.push_str(separator);
+}
+
+fn resolve_link(href: Option<&str>, base_url: &str) -> String {
+    match href {
+        Some(href) if href.starts_with('/') => format!("{base_url}{href}"),
+        Some(href) => href.to_string(),
+        None => String::new(),
+    }
+}
+
+fn attr<'a>(attrs: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    attrs
+        .iter()
+        .find(|(key, _)| key == name)
+        .map(|(_, value)| value.as_str())
+}
+
+/// Collapse runs of whitespace within a paragraph, as a browser would.
+fn collapse_inline_whitespace(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut last_was_space = false;
+    for c in text.chars() {
+        if c.is_whitespace() {
+            if !last_was_space {
+                out.push(' ');
+            }
+            last_was_space = true;
+        } else {
+            out.push(c);
+            last_was_space = false;
+        }
+    }
+    out
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    collapse_inline_whitespace(text).trim().to_string()
+}
+
+fn decode_entities(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let decoded = rest.find(';').and_then(|end| {
+            let entity = &rest[1..end];
+            let c = match entity {
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "amp" => Some('&'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some(' '),
+                _ => entity
+                    .strip_prefix("#x")
+                    .and_then(|x| u32::from_str_radix(x, 16).ok())
+                    .or_else(|| entity.strip_prefix('#').and_then(|x| x.parse().ok()))
+                    .and_then(char::from_u32),
+            }?;
+            Some((c, end + 1))
+        });
+
+        match decoded {
+            Some((c, len)) => {
+                out.push(c);
+                rest = &rest[len..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    out.push_str(rest);
+    out
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Debug, PartialEq)]
+enum Token<'a> {
+    Text(&'a str),
+    Open {
+        name: String,
+        attrs: Vec<(String, String)>,
+    },
+    Close {
+        name: String,
+    },
+}
+
+struct Tokenizer<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Tokenizer<'a> {
+    fn new(html: &'a str) -> Self {
+        Self { rest: html }
+    }
+}
+
+impl<'a> Iterator for Tokenizer<'a> {
+    type Item = Token<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.rest.is_empty() {
+            return None;
+        }
+
+        if !self.rest.starts_with('<') {
+            let end = self.rest.find('<').unwrap_or(self.rest.len());
+            let (text, rest) = self.rest.split_at(end);
+            self.rest = rest;
+            return Some(Token::Text(text));
+        }
+
+        let Some(end) = self.rest.find('>') else {
+            // an unterminated tag is treated as text.
+            let text = self.rest;
+            self.rest = "";
+            return Some(Token::Text(text));
+        };
+
+        let tag = &self.rest[1..end];
+        self.rest = &self.rest[end + 1..];
+
+        if let Some(name) = tag.strip_prefix('/') {
+            return Some(Token::Close {
+                name: name.trim().to_ascii_lowercase(),
+            });
+        }
+
+        let tag = tag.trim_end_matches('/');
+        let (name, attrs) = tag.split_once(char::is_whitespace).unwrap_or((tag, ""));
+
+        Some(Token::Open {
+            name: name.to_ascii_lowercase(),
+            attrs: parse_attrs(attrs),
+        })
+    }
+}
+
+/// Parse `key="value"` pairs. Attributes without a quoted value are skipped.
+fn parse_attrs(s: &str) -> Vec<(String, String)> {
+    let mut attrs = vec![];
+    let mut rest = s.trim();
+
+    while let Some(eq) = rest.find('=') {
+        let key = rest[..eq].trim().to_ascii_lowercase();
+        let value = rest[eq + 1..].trim_start();
+
+        let Some(quote) = value.chars().next().filter(|c| *c == '"' || *c == '\'') else {
+            break;
+        };
+        let Some(end) = value[1..].find(quote) else {
+            break;
+        };
+
+        attrs.push((key, decode_entities(&value[1..=end])));
+        rest = value[end + 2..].trim_start();
+    }
+
+    attrs
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{article_text, puzzle_to_markdown};
+
+    #[test]
+    fn converts_puzzles() {
+        let html = r#"<html><body><main>
+<article class="day-desc"><h2>--- Day 1: Historian Hysteria ---</h2><p>The <em>Chief Historian</em> is
+    always present, see <a href="/2024/about">here</a>.</p>
+<ul><li>Pair <code>3</code> &amp; <code>4</code>.</li><li>Then <code><em>x &lt; y</em></code>.</li></ul>
+<pre><code>3   4
+4   3
+</code></pre>
+<p>What is the <em class="star">total distance</em>?</p>
+</article>
+<p>Your puzzle answer was <code>42</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>Again.</p></article>
+</main></body></html>"#;
+
+        let expected = [
+            "## --- Day 1: Historian Hysteria ---",
+            "",
+            "The *Chief Historian* is always present, see [here](https://adventofcode.com/2024/about).",
+            "",
+            "- Pair `3` & `4`.",
+            "- Then *`x < y`*.",
+            "",
+            "```",
+            "3   4",
+            "4   3",
+            "```",
+            "",
+            "What is the *total distance*?",
+            "",
+            "## --- Part Two ---",
+            "",
+            "Again.",
+            "",
+        ]
+        .join("\n");
+
+        assert_eq!(
+            puzzle_to_markdown(html, "https://adventofcode.com"),
+            expected
+        );
+    }
+
+    #[test]
+    fn extracts_article_text() {
+        let html = "<main>\n<article><p>That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer.</p></article>\n</main>";
+        assert_eq!(
+            article_text(html).as_deref(),
+            Some("That's the right answer! You are one gold star closer.")
+        );
+        assert_eq!(article_text("<p>no article</p>"), None);
+    }
+}
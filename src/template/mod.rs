@@ -1,68 +1,146 @@
-use std::{env, fs};
+use std::{env, path::PathBuf};
 
+pub mod alloc_stats;
+pub mod answer;
 pub mod aoc_cli;
+pub mod aoc_client;
+pub mod bench_config;
 pub mod commands;
+#[cfg(feature = "registry")]
+pub mod registry;
+pub mod report;
 pub mod runner;
 
+pub use answer::{Answer, AsciiArt, PartOutput};
 pub use day::*;
+pub use input::{InputError, InputErrorKind, InputSource};
+pub use year::*;
 
+mod answers;
+mod compare;
 mod day;
+mod examples;
+mod flamegraph;
+mod heap_profile;
+mod input;
+mod markdown;
+mod multi_input;
 mod readme_benchmarks;
 mod run_multi;
+mod stats;
+mod submissions;
 mod timings;
+mod year;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
+pub const ANSI_RED: &str = "\x1b[31m";
+pub const ANSI_GREEN: &str = "\x1b[32m";
 
 /// Helper function that reads a text file to a string.
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    read_year_file(folder, None, day)
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
 pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
+    read_year_file_part(folder, None, day, part)
+}
+
+/// Helper function that reads a text file of `year` to a string, e.g. `data/2023/inputs/01.txt`.
+/// [`None`] reads from the default year.
+///
+/// Panics with a hint on how to fix it if the file is missing, empty or not puzzle data, see [`try_read_year_file`].
+/// Examples may be empty, like the ones created by `scaffold`.
+#[must_use]
+pub fn read_year_file(folder: &str, year: Option<Year>, day: Day) -> String {
+    try_read_year_file(folder, year, day).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// Helper function that reads a text file of `year` to string, appending a part suffix. E.g. like `01-2.txt`.
+#[must_use]
+pub fn read_year_file_part(folder: &str, year: Option<Year>, day: Day, part: u8) -> String {
+    let path = year_file_path(folder, year, &format!("{day}-{part}.txt"));
+    input::load(&path, folder, day).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// Reads a text file of `year` like [`read_year_file`], returning an error if it is missing, empty or
+/// not puzzle data, e.g. an HTML error page. Empty examples are read as empty strings.
+/// Line endings are normalized to `\n`.
+pub fn try_read_year_file(
+    folder: &str,
+    year: Option<Year>,
+    day: Day,
+) -> Result<String, InputError> {
+    let path = year_file_path(folder, year, &format!("{day}.txt"));
+    input::load(&path, folder, day)
+}
+
+fn year_file_path(folder: &str, year: Option<Year>, file_name: &str) -> PathBuf {
+    env::current_dir()
+        .unwrap()
+        .join(data_dir(year))
         .join(folder)
-        .join(format!("{day}-{part}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+        .join(file_name)
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Creates the constants `DAY` and `YEAR` and sets up the input and runner for each part.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+/// Solutions of years other than the default one pass their year last, e.g. `solution!(1, year = 2023)`.
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+        $crate::solution!(@impl $day, None, [part_one, 1] [part_two, 2]);
     };
     ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+        $crate::solution!(@impl $day, None, [part_one, 1]);
     };
     ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+        $crate::solution!(@impl $day, None, [part_two, 2]);
+    };
+    ($day:expr, year = $year:expr) => {
+        $crate::solution!(@impl $day, Some($crate::year!($year)), [part_one, 1] [part_two, 2]);
+    };
+    ($day:expr, 1, year = $year:expr) => {
+        $crate::solution!(@impl $day, Some($crate::year!($year)), [part_one, 1]);
+    };
+    ($day:expr, 2, year = $year:expr) => {
+        $crate::solution!(@impl $day, Some($crate::year!($year)), [part_two, 2]);
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    (@impl $day:expr, $year:expr, $( [$func:expr, $part:expr] )*) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
-        #[cfg(feature = "dhat-heap")]
+        /// The year of the current day, [`None`] for the default year.
+        const YEAR: Option<$crate::template::Year> = $year;
+
+        // the `all` binary links every day, so it cannot have one allocator per day.
+        #[cfg(all(feature = "dhat-heap", not(feature = "registry")))]
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
+        #[cfg(all(feature = "alloc-stats", not(feature = "dhat-heap"), not(feature = "registry")))]
+        #[global_allocator]
+        static ALLOC: $crate::template::alloc_stats::CountingAlloc =
+            $crate::template::alloc_stats::CountingAlloc;
+
         fn main() {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            let input = read_input(YEAR, DAY);
+            $( run_part($func, input.as_str(), YEAR, DAY, $part); )*
+        }
+
+        #[cfg(feature = "registry")]
+        $crate::template::registry::inventory::submit! {
+            $crate::template::registry::Solution::new(YEAR, DAY, |input| {
+                use $crate::template::runner::*;
+                vec![$( run_part($func, input, YEAR, DAY, $part) ),*]
+            })
         }
     };
 }
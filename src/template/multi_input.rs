@@ -0,0 +1,298 @@
+/// Runs a day against every input in a directory, e.g. the inputs of several people, to check that a solution generalizes.
+///
+/// Inputs are the `.txt` files of the directory. The expected answers of an input can be stored next to it,
+/// one line per part, e.g. `alice.answers` for `alice.txt`.
+use std::path::{Path, PathBuf};
+use std::{fs, io};
+
+use crate::template::answers::{self, Verdict};
+use crate::template::bench_config::BenchConfig;
+use crate::template::report::PartReport;
+use crate::template::run_multi::child_commands;
+use crate::template::{bin_path, Day, InputSource, Year, ANSI_GREEN, ANSI_RED, ANSI_RESET};
+
+const PARTS: [u8; 2] = [1, 2];
+
+/// The result of running both parts of a day against one input.
+pub struct InputRun {
+    pub path: PathBuf,
+    pub reports: Vec<PartReport>,
+    /// Why the solution did not report any parts, e.g. because the input was rejected.
+    pub error: Option<String>,
+    /// The expected answers of the input, per part.
+    pub expected: [Option<String>; 2],
+}
+
+impl InputRun {
+    pub fn name(&self) -> String {
+        self.path
+            .file_name()
+            .map_or_else(String::new, |x| x.to_string_lossy().into_owned())
+    }
+
+    pub fn report(&self, part: u8) -> Option<&PartReport> {
+        self.reports.iter().find(|x| x.part == part)
+    }
+
+    pub fn answer(&self, part: u8) -> Option<&str> {
+        self.report(part)?.answer.as_deref()
+    }
+
+    /// The verdict of a part, [`None`] if it neither has an expected answer nor an answer.
+    pub fn verdict(&self, part: u8) -> Option<Verdict> {
+        let expected = self.expected[usize::from(part - 1)].as_deref();
+        let actual = self.answer(part);
+        if expected.is_none() && actual.is_none() {
+            return None;
+        }
+        Some(answers::check(expected, actual))
+    }
+}
+
+/// The input files of `dir`, sorted by name.
+pub fn input_files(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)?
+        .filter_map(Result::ok)
+        .map(|x| x.path())
+        .filter(|x| x.is_file() && x.extension().is_some_and(|x| x == "txt"))
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+/// The file holding the expected answers of an input, e.g. `alice.answers` for `alice.txt`.
+pub fn answers_path(input: &Path) -> PathBuf {
+    input.with_extension("answers")
+}
+
+/// The expected answers of an input, per part. Blank lines stand for unknown answers.
+pub fn read_expected(input: &Path) -> [Option<String>; 2] {
+    let content = fs::read_to_string(answers_path(input)).unwrap_or_default();
+    let mut lines = content.lines().map(|x| {
+        let x = x.trim();
+        (!x.is_empty()).then(|| x.to_string())
+    });
+    [lines.next().flatten(), lines.next().flatten()]
+}
+
+/// Store the expected answers of an input, replacing any previous ones.
+pub fn store_expected(input: &Path, expected: &[Option<String>; 2]) -> io::Result<()> {
+    let lines: Vec<&str> = expected
+        .iter()
+        .map(|x| x.as_deref().unwrap_or_default())
+        .collect();
+    fs::write(answers_path(input), format!("{}\n", lines.join("\n")))
+}
+
+/// Run both parts of `day` against every input in `dir`, one after the other.
+pub fn run(
+    year: Option<Year>,
+    day: Day,
+    dir: &Path,
+    is_release: bool,
+    is_timed: bool,
+    bench_config: &BenchConfig,
+) -> Result<Vec<InputRun>, String> {
+    if !Path::new(&bin_path(year, day)).exists() {
+        return Err(format!("Day {day} has not been scaffolded yet."));
+    }
+
+    let files =
+        input_files(dir).map_err(|e| format!("Failed to read \"{}\": {e}", dir.display()))?;
+    if files.is_empty() {
+        return Err(format!(
+            "\"{}\" does not contain any .txt files.",
+            dir.display()
+        ));
+    }
+
+    child_commands::build_solutions(is_release, bench_config)
+        .map_err(|e| format!("Failed to build solutions: {e}"))?;
+
+    let mut runs = vec![];
+
+    for path in files {
+        let input = InputSource::File(path.clone());
+        let run = child_commands::run_solution_buffered(
+            year,
+            day,
+            is_timed,
+            is_release,
+            bench_config,
+            &input,
+        )
+        .map_err(|e| format!("Failed to run day {day}: {e}"))?;
+
+        let error = run.error.or_else(|| {
+            run.reports
+                .is_empty()
+                .then(|| "no parts were run".to_string())
+        });
+
+        runs.push(InputRun {
+            expected: read_expected(&path),
+            path,
+            reports: run.reports,
+            error,
+        });
+    }
+
+    Ok(runs)
+}
+
+/// A table of the answers and timings of every input, marking answers that match or contradict the expected ones.
+/// Failures are listed below the table.
+pub fn format_table(runs: &[InputRun]) -> String {
+    let header = vec![
+        "Input".to_string(),
+        "Part 1".to_string(),
+        "Part 2".to_string(),
+    ];
+    let mut rows = vec![header];
+    let mut notes = vec![];
+
+    for run in runs {
+        let name = run.name();
+        let mut row = vec![name.clone()];
+
+        for part in PARTS {
+            let cell = match (&run.error, run.report(part)) {
+                (Some(error), _) => {
+                    if part == 1 {
+                        notes.push(format!("{name}: {error}"));
+                    }
+                    "error".to_string()
+                }
+                (
+                    None,
+                    Some(PartReport {
+                        error: Some(error), ..
+                    }),
+                ) => {
+                    notes.push(format!("{name} part {part}: {error}"));
+                    "failed".to_string()
+                }
+                (None, Some(report)) if report.answer.is_some() => {
+                    let answer = report.answer.as_deref().unwrap_or_default();
+                    format!("{answer} ({:.1?})", report.stats.mean_duration())
+                }
+                (None, _) => "-".to_string(),
+            };
+
+            let mark = match run.verdict(part) {
+                Some(Verdict::Pass) => "✔",
+                Some(Verdict::Fail { expected }) => {
+                    if run.error.is_none() {
+                        notes.push(format!(
+                            "{name} part {part}: expected {expected}, got {}",
+                            run.answer(part).unwrap_or("no answer")
+                        ));
+                    }
+                    "✖"
+                }
+                _ => " ",
+            };
+
+            row.push(format!("{mark} {cell}"));
+        }
+
+        rows.push(row);
+    }
+
+    let widths: Vec<usize> = (0..3)
+        .map(|i| rows.iter().map(|x| x[i].chars().count()).max().unwrap_or(0))
+        .collect();
+
+    let mut out = String::new();
+
+    for (i, row) in rows.iter().enumerate() {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:width$}"))
+            .collect();
+        let line = cells.join(" | ");
+        out.push_str(line.trim_end());
+        out.push('\n');
+
+        if i == 0 {
+            let rule: Vec<String> = widths.iter().map(|x| "-".repeat(*x)).collect();
+            out.push_str(&rule.join("-|-"));
+            out.push('\n');
+        }
+    }
+
+    if !notes.is_empty() {
+        out.push('\n');
+        for note in notes {
+            out.push_str(&note);
+            out.push('\n');
+        }
+    }
+
+    // colored after padding, as the escape codes would throw off the widths.
+    out.replace('✔', &format!("{ANSI_GREEN}✔{ANSI_RESET}"))
+        .replace('✖', &format!("{ANSI_RED}✖{ANSI_RESET}"))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{path::PathBuf, time::Duration};
+
+    use super::{answers_path, format_table, InputRun};
+    use crate::template::{
+        report::PartReport, stats::Statistics, ANSI_GREEN, ANSI_RED, ANSI_RESET,
+    };
+
+    fn get_mock_report(part: u8, answer: &str) -> PartReport {
+        PartReport {
+            part,
+            answer: Some(answer.into()),
+            error: None,
+            stats: Statistics::from_single(Duration::from_micros(2)),
+            allocations: None,
+        }
+    }
+
+    #[test]
+    fn pairs_inputs_with_answers() {
+        assert_eq!(
+            answers_path(&PathBuf::from("data/inputs/01/alice.txt")),
+            PathBuf::from("data/inputs/01/alice.answers")
+        );
+    }
+
+    #[test]
+    fn formats_tables() {
+        let runs = [
+            InputRun {
+                path: PathBuf::from("alice.txt"),
+                reports: vec![get_mock_report(1, "11"), get_mock_report(2, "31")],
+                error: None,
+                expected: [Some("11".into()), Some("32".into())],
+            },
+            InputRun {
+                path: PathBuf::from("bob.txt"),
+                reports: vec![],
+                error: Some("\"bob.txt\" is empty".into()),
+                expected: [None, None],
+            },
+        ];
+
+        let expected = [
+            "Input     | Part 1       | Part 2".to_string(),
+            "----------|--------------|-------------".to_string(),
+            format!("alice.txt | {ANSI_GREEN}✔{ANSI_RESET} 11 (2.0µs) | {ANSI_RED}✖{ANSI_RESET} 31 (2.0µs)"),
+            "bob.txt   |   error      |   error".to_string(),
+            String::new(),
+            "alice.txt part 2: expected 32, got 31".to_string(),
+            "bob.txt: \"bob.txt\" is empty".to_string(),
+            String::new(),
+        ]
+        .join("\n");
+
+        assert_eq!(format_table(&runs), expected);
+    }
+}
@@ -2,8 +2,12 @@
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
 use std::{fs, io};
 
+use std::time::Duration;
+
+use crate::template::alloc_stats::AllocStats;
+use crate::template::stats::Statistics;
 use crate::template::timings::Timings;
-use crate::template::Day;
+use crate::template::{bin_path, Year};
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
@@ -25,13 +29,16 @@ pub struct TablePosition {
     pos_end: usize,
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+/// The marker of the table of `year`. Years other than the default one get their own table.
+fn get_marker(year: Option<Year>) -> String {
+    match Year::scoped(year) {
+        Some(year) => format!("<!--- benchmarking table {year} --->"),
+        None => MARKER.into(),
+    }
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
         return Err(Error::Parser(
@@ -52,11 +59,49 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+/// Allocations are appended to the timing of parts that were benched with `--allocs`.
+fn format_cell(
+    timing: Option<String>,
+    stats: Option<&Statistics>,
+    allocations: Option<&AllocStats>,
+    show_stats: bool,
+    is_failed: bool,
+) -> String {
+    let Some(timing) = timing else {
+        return if is_failed { "`failed`" } else { "`-`" }.into();
+    };
+
+    let cell = match stats {
+        Some(stats) if show_stats => {
+            let median = Duration::from_nanos(stats.median);
+            let min = Duration::from_nanos(stats.min);
+            let std_dev = Duration::from_nanos(stats.std_dev);
+            format!("`{timing}` (median `{median:.1?}`, min `{min:.1?}`, σ `{std_dev:.1?}`)")
+        }
+        _ => format!("`{timing}`"),
+    };
+
+    match allocations {
+        Some(allocations) => format!("{cell} · {allocations}"),
+        None => cell,
+    }
+}
+
+fn construct_table(
+    prefix: &str,
+    timings: Timings,
+    total_millis: f64,
+    show_stats: bool,
+    year: Option<Year>,
+) -> String {
+    let marker = get_marker(year);
+    let header = match Year::scoped(year) {
+        Some(year) => format!("{prefix} Benchmarks ({year})"),
+        None => format!("{prefix} Benchmarks"),
+    };
 
     let mut lines: Vec<String> = vec![
-        MARKER.into(),
+        marker.clone(),
         header,
         String::new(),
         "| Day | Part 1 | Part 2 |".into(),
@@ -64,35 +109,65 @@ fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String
     ];
 
     for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
+        let path = bin_path(year, timing.day);
         lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
+            "| [Day {}]({}) | {} | {} |",
             timing.day.into_inner(),
             path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
+            format_cell(
+                timing.part_1,
+                timing.part_1_stats.as_ref(),
+                timing.part_1_allocations.as_ref(),
+                show_stats,
+                timing.failed_parts.contains(&1)
+            ),
+            format_cell(
+                timing.part_2,
+                timing.part_2_stats.as_ref(),
+                timing.part_2_allocations.as_ref(),
+                show_stats,
+                timing.failed_parts.contains(&2)
+            ),
         ));
     }
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
+    lines.push(marker);
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+fn update_content(
+    s: &mut String,
+    timings: Timings,
+    total_millis: f64,
+    show_stats: bool,
+    year: Option<Year>,
+) -> Result<(), Error> {
+    let marker = get_marker(year);
+
+    // tables of other years are added below the existing benchmarks on first use.
+    if Year::scoped(year).is_some() && !s.contains(&marker) {
+        let slot = format!("\n\n{marker}{marker}");
+        match locate_table(s, MARKER) {
+            Ok(positions) => s.insert_str(positions.pos_end, &slot),
+            Err(_) => s.push_str(&slot),
+        }
+    }
+
+    let positions = locate_table(s, &marker)?;
+    let table = construct_table("##", timings, total_millis, show_stats, year);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
-pub fn update(timings: Timings) -> Result<(), Error> {
+/// Replace the benchmarking table of `year` in the readme. With `show_stats`, cells include median, min and standard deviation where available.
+pub fn update(timings: Timings, show_stats: bool, year: Option<Year>) -> Result<(), Error> {
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
     let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+    update_content(&mut readme, timings, total_millis, show_stats, year)?;
     fs::write(path, &readme)?;
     Ok(())
 }
@@ -100,27 +175,46 @@ pub fn update(timings: Timings) -> Result<(), Error> {
 #[cfg(feature = "test_lib")]
 mod tests {
     use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use crate::{
+        day, template::alloc_stats::AllocStats, template::stats::Statistics,
+        template::timings::Timing, template::timings::Timings, year,
+    };
 
     fn get_mock_timings() -> Timings {
         Timings {
+            history: vec![],
             data: vec![
                 Timing {
                     day: day!(1),
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_allocations: None,
+                    part_2_allocations: None,
+                    failed_parts: vec![],
                     total_nanos: 3e+10,
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_allocations: None,
+                    part_2_allocations: None,
+                    failed_parts: vec![],
                     total_nanos: 7e+10,
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: Some("50ms".into()),
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_allocations: None,
+                    part_2_allocations: None,
+                    failed_parts: vec![],
                     total_nanos: 9e+10,
                 },
             ],
@@ -131,28 +225,28 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, false, None).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, false, None).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, false, None).unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, false, None).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, false, None).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -160,7 +254,7 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, false, None).unwrap();
         let expected = [
             "foo",
             "bar",
@@ -180,4 +274,51 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_benchmarks_with_stats() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1_stats = Some(Statistics {
+            median: 9_500_000,
+            min: 9_000_000,
+            std_dev: 250_000,
+            ..Statistics::default()
+        });
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0, true, None).unwrap();
+        assert!(s.contains(
+            "| [Day 1](./src/bin/01.rs) | `10ms` (median `9.5ms`, min `9.0ms`, σ `250.0µs`) | `20ms` |"
+        ));
+    }
+
+    #[test]
+    fn format_benchmarks_with_allocations() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_2_allocations = Some(AllocStats {
+            allocations: 7,
+            bytes: 2048,
+            peak_bytes: 232,
+        });
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0, false, None).unwrap();
+        assert!(s.contains(
+            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` · 7 allocations, 2.0 KiB, peak 232 B |"
+        ));
+    }
+
+    #[test]
+    fn adds_tables_for_other_years() {
+        // NOTE: `AOC_YEAR` is set to 2024 in `.cargo/config.toml`.
+        let mut s = format!("foo\n{}{}\nbaz", MARKER, MARKER);
+        update_content(&mut s, get_mock_timings(), 190.0, false, Some(year!(2023))).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, false, Some(year!(2023))).unwrap();
+
+        assert_eq!(s.matches("<!--- benchmarking table 2023 --->").count(), 2);
+        assert_eq!(s.matches("## Benchmarks (2023)").count(), 1);
+        assert!(s.contains("| [Day 1](./src/bin/2023_01.rs) | `10ms` | `20ms` |"));
+        assert!(s.starts_with(&format!("foo\n{}{}\n\n", MARKER, MARKER)));
+        assert!(s.ends_with("<!--- benchmarking table 2023 --->\nbaz"));
+    }
 }
@@ -0,0 +1,83 @@
+/// Inventory of all solutions, used to run every day in-process from the `all` binary.
+///
+/// With the `registry` feature, the [`crate::solution`] macro submits every day to this inventory.
+use std::panic::{self, AssertUnwindSafe};
+use std::process;
+
+#[doc(hidden)]
+pub use inventory;
+
+use crate::template::report::PartReport;
+use crate::template::{
+    try_read_year_file, Day, InputError, InputErrorKind, Year, ANSI_BOLD, ANSI_RESET,
+};
+
+/// A registered solution: runs all parts of `day` against an input.
+pub struct Solution {
+    /// [`None`] for the default year.
+    pub year: Option<Year>,
+    pub day: Day,
+    pub run: fn(&str) -> Vec<PartReport>,
+}
+
+impl Solution {
+    pub const fn new(year: Option<Year>, day: Day, run: fn(&str) -> Vec<PartReport>) -> Self {
+        Self { year, day, run }
+    }
+}
+
+inventory::collect!(Solution);
+
+/// All registered solutions of `year`, sorted by day.
+pub fn solutions(year: Option<Year>) -> Vec<&'static Solution> {
+    let mut solutions: Vec<_> = inventory::iter::<Solution>
+        .into_iter()
+        .filter(|x| Year::scoped(x.year) == Year::scoped(year))
+        .collect();
+    solutions.sort_unstable_by_key(|x| x.day);
+    solutions
+}
+
+/// Run every registered solution against its input, in order and within the current process.
+/// Accepts `--year`. Timings are not kept, so `--time` is rejected in favour of `cargo time`.
+pub fn run_all() {
+    let args: Vec<String> = std::env::args().collect();
+    if args.iter().any(|x| x == "--time") {
+        eprintln!("The `all` binary does not keep timings. Use `cargo time` instead.");
+        process::exit(1);
+    }
+    let year = args
+        .iter()
+        .position(|x| x == "--year")
+        .and_then(|i| args.get(i + 1)?.parse().ok());
+
+    for (i, solution) in solutions(year).into_iter().enumerate() {
+        let day = solution.day;
+
+        if i > 0 {
+            println!();
+        }
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
+
+        let input = match try_read_year_file("inputs", year, day) {
+            Ok(input) => input,
+            Err(InputError {
+                kind: InputErrorKind::Missing,
+                ..
+            }) => {
+                println!("No input.");
+                continue;
+            }
+            Err(e) => {
+                println!("{e}");
+                continue;
+            }
+        };
+
+        // a panicking solution should not take down the remaining days. the panic hook prints the message.
+        if panic::catch_unwind(AssertUnwindSafe(|| (solution.run)(&input))).is_err() {
+            println!("Panicked.");
+        }
+    }
+}
@@ -0,0 +1,190 @@
+/// Machine-readable output of solution binaries, emitted one JSON record per line with `--json`.
+use std::{collections::HashMap, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::alloc_stats::AllocStats;
+use crate::template::stats::Statistics;
+
+/// Result and timing of a single solution part.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartReport {
+    pub part: u8,
+    /// The displayed answer, [`None`] if the part returned no result.
+    pub answer: Option<String>,
+    /// The error of a part that failed, as opposed to one that is not implemented yet.
+    pub error: Option<String>,
+    pub stats: Statistics,
+    /// Heap allocations of the part, only counted with `--allocs`.
+    pub allocations: Option<AllocStats>,
+}
+
+impl PartReport {
+    pub fn is_success(&self) -> bool {
+        self.answer.is_some()
+    }
+
+    pub fn is_failure(&self) -> bool {
+        self.error.is_some()
+    }
+
+    /// Serializes the report into a single line of JSON.
+    pub fn to_json_line(&self) -> String {
+        JsonValue::from(self)
+            .stringify()
+            .expect("part report to be serializable")
+    }
+
+    /// Parses a line emitted by [`PartReport::to_json_line`], returns [`None`] for any other output.
+    pub fn from_json_line(line: &str) -> Option<Self> {
+        let json = JsonValue::from_str(line.trim()).ok()?;
+        PartReport::try_from(&json).ok()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&PartReport> for JsonValue {
+    fn from(value: &PartReport) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("part".into(), JsonValue::Number(value.part.into()));
+        map.insert("success".into(), JsonValue::Boolean(value.is_success()));
+        map.insert(
+            "answer".into(),
+            match &value.answer {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert(
+            "error".into(),
+            match &value.error {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert("stats".into(), JsonValue::from(&value.stats));
+        map.insert(
+            "allocations".into(),
+            match &value.allocations {
+                Some(x) => JsonValue::from(x),
+                None => JsonValue::Null,
+            },
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartReport {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected report to be a JSON object.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected report.part to be a number.")? as u8;
+
+        let answer = json
+            .get("answer")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected report.answer to be null or string.")?;
+
+        let error = json.get("error").and_then(|v| v.get::<String>()).cloned();
+
+        let stats = json
+            .get("stats")
+            .ok_or("Expected report to have key `stats`.")?;
+
+        let allocations = match json.get("allocations") {
+            Some(v) if !v.is_null() => Some(AllocStats::try_from(v)?),
+            _ => None,
+        };
+
+        Ok(PartReport {
+            part,
+            answer: answer.cloned(),
+            error,
+            stats: Statistics::try_from(stats)?,
+            allocations,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::PartReport;
+    use crate::template::alloc_stats::AllocStats;
+    use crate::template::stats::Statistics;
+    use std::time::Duration;
+
+    fn get_mock_report() -> PartReport {
+        PartReport {
+            part: 1,
+            answer: Some("a (b) @ 5 samples)\nc".into()),
+            error: None,
+            stats: Statistics::from_samples(&[60, 70, 74, 90, 1_200].map(Duration::from_nanos)),
+            allocations: None,
+        }
+    }
+
+    #[test]
+    fn roundtrips_reports() {
+        let report = get_mock_report();
+        let line = report.to_json_line();
+        assert!(!line.contains('\n'));
+        assert_eq!(PartReport::from_json_line(&line), Some(report));
+    }
+
+    #[test]
+    fn roundtrips_missing_answers() {
+        let report = PartReport {
+            answer: None,
+            ..get_mock_report()
+        };
+        let parsed = PartReport::from_json_line(&report.to_json_line()).unwrap();
+        assert_eq!(parsed.is_success(), false);
+    }
+
+    #[test]
+    fn roundtrips_errors() {
+        let report = PartReport {
+            answer: None,
+            error: Some("invalid digit found in string".into()),
+            ..get_mock_report()
+        };
+        let parsed = PartReport::from_json_line(&report.to_json_line()).unwrap();
+        assert!(parsed.is_failure());
+        assert_eq!(parsed, report);
+    }
+
+    #[test]
+    fn roundtrips_allocations() {
+        let report = PartReport {
+            allocations: Some(AllocStats {
+                allocations: 3,
+                bytes: 276,
+                peak_bytes: 232,
+            }),
+            ..get_mock_report()
+        };
+        assert_eq!(
+            PartReport::from_json_line(&report.to_json_line()),
+            Some(report)
+        );
+    }
+
+    #[test]
+    fn ignores_other_output() {
+        assert_eq!(PartReport::from_json_line("Part 1: 42 (1.0ms)"), None);
+        assert_eq!(PartReport::from_json_line("{}"), None);
+        assert_eq!(PartReport::from_json_line(""), None);
+    }
+}
@@ -1,41 +1,45 @@
-use std::{collections::HashSet, io};
+use std::{
+    collections::{BTreeMap, HashSet},
+    fmt::Display,
+    io, process,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{
+    bench_config::BenchConfig, Day, InputSource, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RED, ANSI_RESET,
+};
 
 use super::{
     all_days,
+    report::PartReport,
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
-    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
-
-    let mut need_space = false;
-
-    // NOTE: use non-duplicate, sorted day values.
-    all_days()
-        .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
-            }
-            need_space = true;
-
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
-
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
-
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
-            }
-        });
+/// Run the solutions of `days_to_run`, using up to `jobs` concurrent processes.
+/// Timed runs are always sequential, so that solutions do not compete for the CPU while being benched.
+pub fn run_multi(
+    year: Option<Year>,
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    is_timed: bool,
+    bench_config: &BenchConfig,
+    jobs: usize,
+) -> Option<Timings> {
+    let reports = run_multi_reports(year, days_to_run, is_release, is_timed, bench_config, jobs);
 
     if is_timed {
-        let timings = Timings { data: timings };
+        let timings = Timings {
+            data: reports
+                .iter()
+                .filter(|(_, reports)| !reports.is_empty())
+                .map(|(day, reports)| child_commands::collect_timing(reports, *day))
+                .collect(),
+            history: vec![],
+        };
         let total_millis = timings.total_millis();
         println!(
             "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
@@ -46,11 +50,155 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
     }
 }
 
+/// Run the solutions of `days_to_run` in order, printing their output, and return the reports of each day.
+/// Days without reports have not been scaffolded or solved yet.
+pub fn run_multi_reports(
+    year: Option<Year>,
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    is_timed: bool,
+    bench_config: &BenchConfig,
+    jobs: usize,
+) -> Vec<(Day, Vec<PartReport>)> {
+    // NOTE: use non-duplicate, sorted day values.
+    let days: Vec<Day> = all_days().filter(|day| days_to_run.contains(day)).collect();
+
+    let results = if jobs > 1 && !is_timed {
+        run_parallel(year, &days, is_release, bench_config, jobs)
+    } else {
+        days.iter()
+            .enumerate()
+            .map(|(i, &day)| {
+                print_header(day, i == 0);
+
+                let reports =
+                    child_commands::run_solution(year, day, is_timed, is_release, bench_config)
+                        .unwrap();
+
+                if reports.is_empty() {
+                    println!("Not solved.");
+                }
+
+                (day, reports)
+            })
+            .collect()
+    };
+
+    print_failures(&results);
+    results
+}
+
+/// List the parts that failed, which are easy to miss in between parts that are not implemented yet.
+fn print_failures(results: &[(Day, Vec<PartReport>)]) {
+    let failures: Vec<String> = results
+        .iter()
+        .flat_map(|(day, reports)| {
+            reports
+                .iter()
+                .filter(|r| r.is_failure())
+                .map(move |r| format!("day {day} part {}", r.part))
+        })
+        .collect();
+
+    if !failures.is_empty() {
+        println!("\n{ANSI_RED}Failed:{ANSI_RESET} {}", failures.join(", "));
+    }
+}
+
+/// Run `days` on `jobs` worker threads. Output of each day is buffered and printed in day order
+/// as soon as all previous days have finished.
+fn run_parallel(
+    year: Option<Year>,
+    days: &[Day],
+    is_release: bool,
+    bench_config: &BenchConfig,
+    jobs: usize,
+) -> Vec<(Day, Vec<PartReport>)> {
+    // build once up front, so that workers do not wait on each other for cargo's build lock.
+    if let Err(e) = child_commands::build_solutions(is_release, bench_config) {
+        eprintln!("Failed to build solutions: {e}");
+        process::exit(1);
+    }
+
+    let next = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(days.len()) {
+            let tx = tx.clone();
+            let next = &next;
+            scope.spawn(move || loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let Some(&day) = days.get(i) else {
+                    break;
+                };
+                let run = child_commands::run_solution_buffered(
+                    year,
+                    day,
+                    false,
+                    is_release,
+                    bench_config,
+                    &InputSource::Puzzle,
+                )
+                .unwrap_or_else(|e| child_commands::BufferedRun::failed(day, e.to_string()));
+                if tx.send((i, run)).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(tx);
+
+        let mut pending = BTreeMap::new();
+        let mut results = Vec::with_capacity(days.len());
+
+        for (i, run) in rx {
+            pending.insert(i, run);
+
+            while let Some(run) = pending.remove(&results.len()) {
+                let day = days[results.len()];
+                print_header(day, results.is_empty());
+
+                print!("{}", run.stdout);
+                eprint!("{}", run.stderr);
+                if let Some(error) = &run.error {
+                    eprintln!("{ANSI_RED}Error:{ANSI_RESET} {error}");
+                }
+                if run.reports.is_empty() {
+                    println!("Not solved.");
+                }
+
+                results.push((day, run.reports));
+            }
+        }
+
+        results
+    })
+}
+
+fn print_header(day: Day, is_first: bool) {
+    if !is_first {
+        println!();
+    }
+    println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+    println!("------");
+}
+
 #[allow(dead_code)]
 #[derive(Debug)]
 pub enum Error {
     BrokenPipe,
     IO(io::Error),
+    BuildFailed(process::ExitStatus),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::BrokenPipe => write!(f, "the output of the solution could not be read."),
+            Error::IO(e) => write!(f, "{e}"),
+            Error::BuildFailed(status) => write!(f, "`cargo build` exited with {status}."),
+        }
+    }
 }
 
 impl From<std::io::Error> for Error {
@@ -59,136 +207,307 @@ impl From<std::io::Error> for Error {
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
 /// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// This module encapsulates interaction with these binaries, both invoking them as well as collecting their reports.
 pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use super::Error;
+    use crate::template::{
+        bench_config::BenchConfig,
+        bin_name, bin_path,
+        report::PartReport,
+        runner::{format_allocations, format_duration, format_failure, format_result},
+        stats::Statistics,
+        Day, InputSource, Year,
+    };
     use std::{
         io::{BufRead, BufReader},
         path::Path,
-        process::{Command, Stdio},
+        process::{Child, Command, ExitStatus, Stdio},
         thread,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
-        }
+    /// Output of a solution bin that was captured instead of printed.
+    pub struct BufferedRun {
+        pub reports: Vec<PartReport>,
+        /// Rendered reports and any other output, ready to be printed.
+        pub stdout: String,
+        pub stderr: String,
+        /// Why the bin exited with an error, e.g. because its input was rejected.
+        pub error: Option<String>,
+    }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+    impl BufferedRun {
+        /// A run of a bin that could not be started, with every part marked as failed.
+        pub fn failed(day: Day, error: String) -> Self {
+            let reports = failed_reports(&[], &error);
+            let stdout = reports.iter().map(|x| format_report(x, day)).collect();
+            BufferedRun {
+                reports,
+                stdout,
+                stderr: String::new(),
+                error: Some(error),
+            }
+        }
+    }
 
+    /// Build all solution bins, so that subsequent `cargo run` invocations do not need to.
+    pub fn build_solutions(is_release: bool, bench_config: &BenchConfig) -> Result<(), Error> {
+        let mut args: Vec<String> = vec!["build".into(), "--quiet".into(), "--bins".into()];
         if is_release {
-            args.push("--release");
+            args.push("--release".into());
+        }
+        args.extend(bench_config.cargo_args());
+        let status = Command::new("cargo").args(&args).status()?;
+        if !status.success() {
+            return Err(Error::BuildFailed(status));
         }
+        Ok(())
+    }
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+    /// Run the solution bin for a given day
+    pub fn run_solution(
+        year: Option<Year>,
+        day: Day,
+        is_timed: bool,
+        is_release: bool,
+        bench_config: &BenchConfig,
+    ) -> Result<Vec<PartReport>, Error> {
+        // skip command invocation for days that have not been scaffolded yet.
+        if !Path::new(&bin_path(year, day)).exists() {
+            return Ok(vec![]);
         }
 
         // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        // render reports to stdout, forward any other output as-is.
 
-        let mut cmd = Command::new("cargo")
-            .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
+        let mut cmd = spawn_solution(
+            year,
+            day,
+            is_timed,
+            is_release,
+            bench_config,
+            &InputSource::Puzzle,
+        )?;
 
         let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
         let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
 
-        let mut output = vec![];
+        let mut reports = vec![];
 
         let thread = thread::spawn(move || {
+            let mut last_line = None;
             stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
+                let line = line.unwrap();
+                eprintln!("{line}");
+                if !line.trim().is_empty() {
+                    last_line = Some(line);
+                }
             });
+            last_line
         });
 
         for line in stdout.lines() {
             let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+            match PartReport::from_json_line(&line) {
+                Some(report) => {
+                    print_report(&report, day);
+                    reports.push(report);
+                }
+                None => println!("{line}"),
+            }
         }
 
-        thread.join().unwrap();
-        cmd.wait()?;
+        let last_line = thread.join().unwrap();
+        let status = cmd.wait()?;
 
-        Ok(output)
+        if !status.success() {
+            let error = exit_error(status, last_line.as_deref());
+            for report in failed_reports(&reports, &error) {
+                print_report(&report, day);
+                reports.push(report);
+            }
+        }
+
+        Ok(reports)
+    }
+
+    /// The reason a bin exited with `status`, preferring the last line it printed to stderr.
+    fn exit_error(status: ExitStatus, last_line: Option<&str>) -> String {
+        last_line.map_or_else(|| format!("exited with {status}"), ToString::to_string)
+    }
+
+    /// Reports of the parts that did not report before the bin exited with an error, marked as failed.
+    /// Without them, a day that fails before its parts run would look as if it was not solved yet.
+    fn failed_reports(reports: &[PartReport], error: &str) -> Vec<PartReport> {
+        [1, 2]
+            .into_iter()
+            .filter(|part| !reports.iter().any(|x| x.part == *part))
+            .map(|part| PartReport {
+                part,
+                answer: None,
+                error: Some(error.to_string()),
+                stats: Statistics::default(),
+                allocations: None,
+            })
+            .collect()
+    }
+
+    /// Run the solution bin for a given day, capturing its output instead of printing it.
+    pub fn run_solution_buffered(
+        year: Option<Year>,
+        day: Day,
+        is_timed: bool,
+        is_release: bool,
+        bench_config: &BenchConfig,
+        input: &InputSource,
+    ) -> Result<BufferedRun, Error> {
+        let mut run = BufferedRun {
+            reports: vec![],
+            stdout: String::new(),
+            stderr: String::new(),
+            error: None,
+        };
+
+        // skip command invocation for days that have not been scaffolded yet.
+        if !Path::new(&bin_path(year, day)).exists() {
+            return Ok(run);
+        }
+
+        let output = spawn_solution(year, day, is_timed, is_release, bench_config, input)?
+            .wait_with_output()?;
+
+        for line in String::from_utf8_lossy(&output.stdout).lines() {
+            match PartReport::from_json_line(line) {
+                Some(report) => {
+                    run.stdout.push_str(&format_report(&report, day));
+                    run.reports.push(report);
+                }
+                None => {
+                    run.stdout.push_str(line);
+                    run.stdout.push('\n');
+                }
+            }
+        }
+
+        run.stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+
+        if !output.status.success() {
+            let last_line = run.stderr.lines().rfind(|x| !x.trim().is_empty());
+            let error = exit_error(output.status, last_line);
+            for report in failed_reports(&run.reports, &error) {
+                run.stdout.push_str(&format_report(&report, day));
+                run.reports.push(report);
+            }
+            run.error = Some(error);
+        }
+
+        Ok(run)
+    }
+
+    fn spawn_solution(
+        year: Option<Year>,
+        day: Day,
+        is_timed: bool,
+        is_release: bool,
+        bench_config: &BenchConfig,
+        input: &InputSource,
+    ) -> Result<Child, Error> {
+        let mut args: Vec<String> = vec![
+            "run".into(),
+            "--quiet".into(),
+            "--bin".into(),
+            bin_name(year, day),
+        ];
+
+        if is_release {
+            args.push("--release".into());
+        }
+
+        args.extend(bench_config.cargo_args());
+
+        // request one machine-readable report per part instead of human-readable output.
+        args.push("--".into());
+        args.push("--json".into());
+
+        if is_timed {
+            // mirror `--time` flag and the bench budget to child invocations.
+            args.push("--time".into());
+            args.extend(bench_config.to_args(year, day));
+        }
+
+        args.extend(input.to_args());
+
+        Ok(Command::new("cargo")
+            .args(&args)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?)
+    }
+
+    fn print_report(report: &PartReport, day: Day) {
+        print!("{}", format_report(report, day));
+    }
+
+    fn format_report(report: &PartReport, day: Day) -> String {
+        let part = format!("Part {}", report.part);
+        match &report.error {
+            Some(error) => format_failure(day, &part, error),
+            None => {
+                let measurements = format!(
+                    "{}{}",
+                    format_duration(&report.stats),
+                    format_allocations(report.allocations.as_ref())
+                );
+                format_result(&report.answer, &part, &measurements)
+            }
+        }
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
+    /// Collect the benchmark times of successfully solved parts into a [`super::Timing`].
+    pub fn collect_timing(reports: &[PartReport], day: Day) -> super::Timing {
         let mut timings = super::Timing {
             day,
             part_1: None,
             part_2: None,
+            part_1_stats: None,
+            part_2_stats: None,
+            part_1_allocations: None,
+            part_2_allocations: None,
+            failed_parts: vec![],
             total_nanos: 0_f64,
         };
 
-        output
+        timings.failed_parts = reports
             .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
+            .filter(|r| r.is_failure())
+            .map(|r| r.part)
+            .collect();
+
+        for report in reports.iter().filter(|r| r.is_success()) {
+            let timing_str = format!("{:.1?}", report.stats.mean_duration());
+            let stats = Some(report.stats.clone());
+            let allocations = report.allocations.clone();
+            match report.part {
+                1 => {
+                    (timings.part_1, timings.part_1_stats) = (Some(timing_str), stats);
+                    timings.part_1_allocations = allocations;
                 }
-
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
-
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
+                2 => {
+                    (timings.part_2, timings.part_2_stats) = (Some(timing_str), stats);
+                    timings.part_2_allocations = allocations;
                 }
+                _ => continue,
+            }
 
-                timings.total_nanos += nanos;
-            });
+            #[allow(clippy::cast_precision_loss)]
+            {
+                timings.total_nanos += report.stats.mean as f64;
+            }
+        }
 
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
-
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
-    }
-
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
     #[cfg(feature = "test_lib")]
     macro_rules! assert_approx_eq {
@@ -205,53 +524,110 @@ pub mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::{collect_timing, failed_reports, BufferedRun};
 
-        use crate::day;
+        use crate::{
+            day,
+            template::{alloc_stats::AllocStats, report::PartReport, stats::Statistics},
+        };
+
+        fn get_mock_report(part: u8, answer: Option<&str>, mean_nanos: u64) -> PartReport {
+            PartReport {
+                part,
+                answer: answer.map(Into::into),
+                error: None,
+                stats: Statistics {
+                    samples: 100,
+                    mean: mean_nanos,
+                    median: mean_nanos,
+                    min: mean_nanos,
+                    max: mean_nanos,
+                    ..Statistics::default()
+                },
+                allocations: None,
+            }
+        }
 
         #[test]
-        fn parses_execution_times() {
-            let res = parse_exec_time(
+        fn collects_execution_times() {
+            let res = collect_timing(
                 &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
+                    get_mock_report(1, Some("0"), 74_130),
+                    get_mock_report(2, Some("10"), 74_130_000),
                 ],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_approx_eq!(res.total_nanos, 74204130_f64);
+            assert_eq!(res.part_1.unwrap(), "74.1µs");
+            assert_eq!(res.part_2.unwrap(), "74.1ms");
+            assert_eq!(res.part_1_stats.unwrap().samples, 100);
         }
 
         #[test]
-        fn parses_with_patterns_in_input() {
-            let res = parse_exec_time(
+        fn fails_parts_without_reports() {
+            let reports = failed_reports(&[get_mock_report(1, Some("1"), 100)], "input is empty");
+            assert_eq!(reports.len(), 1);
+            assert_eq!(reports[0].part, 2);
+            assert_eq!(reports[0].error.as_deref(), Some("input is empty"));
+
+            let timing = collect_timing(&failed_reports(&[], "input is empty"), day!(1));
+            assert_eq!(timing.failed_parts, [1, 2]);
+        }
+
+        #[test]
+        fn fails_runs_that_could_not_start() {
+            let run = BufferedRun::failed(day!(1), "program not found".into());
+            assert_eq!(run.error.as_deref(), Some("program not found"));
+            assert_eq!(run.reports.len(), 2);
+            assert!(run.reports.iter().all(PartReport::is_failure));
+            assert!(run.stdout.contains("program not found"));
+        }
+
+        #[test]
+        fn collects_allocations() {
+            let allocations = AllocStats {
+                allocations: 3,
+                bytes: 276,
+                peak_bytes: 232,
+            };
+            let res = collect_timing(
                 &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
+                    PartReport {
+                        allocations: Some(allocations.clone()),
+                        ..get_mock_report(1, Some("0"), 100)
+                    },
+                    get_mock_report(2, Some("10"), 100),
                 ],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_eq!(res.part_1_allocations, Some(allocations));
+            assert_eq!(res.part_2_allocations, None);
         }
 
         #[test]
-        fn parses_missing_parts() {
-            let res = parse_exec_time(
+        fn collects_with_patterns_in_answer() {
+            let res = collect_timing(
                 &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
+                    get_mock_report(1, Some("@ @ @ ( ) ms (2s @ 5 samples)"), 2_000_000_000),
+                    get_mock_report(2, Some("10s"), 100_000_000),
                 ],
                 day!(1),
             );
+            assert_approx_eq!(res.total_nanos, 2100000000_f64);
+            assert_eq!(res.part_1.unwrap(), "2.0s");
+            assert_eq!(res.part_2.unwrap(), "100.0ms");
+        }
+
+        #[test]
+        fn collects_missing_parts() {
+            let res = collect_timing(
+                &[get_mock_report(1, None, 100), get_mock_report(2, None, 100)],
+                day!(1),
+            );
             assert_approx_eq!(res.total_nanos, 0_f64);
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
+            assert_eq!(res.part_1_stats.is_none(), true);
         }
     }
 }
@@ -2,64 +2,274 @@
 use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{stdout, Write};
-use std::process::Output;
-use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use std::{env, process};
 
+use crate::template::alloc_stats::{self, AllocStats};
+use crate::template::aoc_client::{self, AocClientError};
+use crate::template::bench_config::BenchConfig;
+use crate::template::report::PartReport;
+use crate::template::stats::Statistics;
+use crate::template::submissions::{self, Outcome, Submission};
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{
+    answers, Answer, Day, InputSource, PartOutput, Year, ANSI_ITALIC, ANSI_RED, ANSI_RESET,
+};
 
-pub fn run_part<I: Copy, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// Read the input of a solution binary, from `data/inputs` unless `--input` or `--example` is passed.
+/// Exits with a hint on how to fix it if the input can't be used.
+pub fn read_input(year: Option<Year>, day: Day) -> String {
+    let source = InputSource::from_args().unwrap_or_else(|e| {
+        eprintln!("Unexpected command-line input: {e}.");
+        process::exit(1);
+    });
+
+    if source != InputSource::Puzzle {
+        // answers to other inputs can't be submitted, nor stored as the known answers of the puzzle.
+        if submit_part().is_some() {
+            eprintln!("Only answers to the puzzle input can be submitted, not to {source}.");
+            process::exit(1);
+        }
+        if !is_json_output() {
+            println!("{ANSI_ITALIC}Reading input from {source}.{ANSI_RESET}");
+        }
+    }
+
+    source.read(year, day).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        process::exit(1);
+    })
+}
+
+/// Run a solution part, print its result and return a report of it.
+/// The report holds the answer as it is submitted, see [`Answer::submission`].
+/// Parts that return an error or panic are reported as failed.
+pub fn run_part<I: Copy, R: PartOutput>(
+    func: impl Fn(I) -> R,
+    input: I,
+    year: Option<Year>,
+    day: Day,
+    part: u8,
+) -> PartReport {
     let part_str = format!("Part {part}");
+    let is_json = is_json_output();
+
+    let (result, stats, allocations) = run_timed(
+        |input| func(input).into_result(),
+        input,
+        year,
+        day,
+        is_json,
+        |result| {
+            if let (false, Ok(answer)) = (is_json, result) {
+                print_result(&answer.as_ref().map(Answer::display), &part_str, "");
+            }
+        },
+    );
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let (answer, error) = match result.and_then(|result| result) {
+        Ok(answer) => (answer, None),
+        Err(e) => (None, Some(e)),
+    };
+
+    let displayed = answer.as_ref().map(Answer::display);
+    let submission = answer.as_ref().and_then(Answer::submission);
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    let report = PartReport {
+        part,
+        answer: submission.clone().or_else(|| displayed.clone()),
+        error,
+        stats,
+        allocations,
+    };
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    if is_json {
+        println!("{}", report.to_json_line());
+    } else if let Some(error) = &report.error {
+        print!("\r{}", format_failure(day, &part_str, error));
+    } else {
+        let measurements = format!(
+            "{}{}",
+            format_duration(&report.stats),
+            format_allocations(report.allocations.as_ref())
+        );
+        print_result(&displayed, &part_str, &measurements);
+        // e.g. ascii art that spells letters, which are easier to read off this way.
+        if let Some(submission) = submission
+            .as_ref()
+            .filter(|x| Some(*x) != displayed.as_ref())
+        {
+            println!("{part_str} (as submitted): {ANSI_BOLD}{submission}{ANSI_RESET}");
+        }
     }
+
+    if displayed.is_some() && submission.is_none() && submit_part() == Some(part) {
+        eprintln!("Refusing to submit part {part}: the answer can't be converted for submission.");
+    }
+
+    if let Some(answer) = &submission {
+        match submit_result(answer, year, day, part) {
+            Some(Ok(message)) => {
+                println!("{message}");
+                let outcome = Outcome::from_message(&message);
+                let wait_seconds = submissions::parse_cooldown(&message);
+                record_submission(year, day, part, answer, outcome, wait_seconds);
+                if outcome == Outcome::Correct {
+                    store_answer(year, day, part, answer);
+                }
+            }
+            Some(Err(e)) => {
+                eprintln!("Failed to submit result: {e}");
+                if let Some(outcome) = e.outcome() {
+                    let wait_seconds = submissions::parse_cooldown(&e.to_string());
+                    record_submission(year, day, part, answer, outcome, wait_seconds);
+                }
+            }
+            None => {}
+        }
+    }
+
+    report
+}
+
+/// Append a submission and its verdict to the history of the part.
+fn record_submission(
+    year: Option<Year>,
+    day: Day,
+    part: u8,
+    answer: &str,
+    outcome: Outcome,
+    wait_seconds: Option<u64>,
+) {
+    let submission = Submission {
+        timestamp: current_timestamp(),
+        answer: answer.to_string(),
+        outcome,
+        wait_seconds,
+    };
+    if let Err(e) = submissions::record(year, day, part, &submission) {
+        eprintln!("Failed to record submission: {e}");
+    }
+}
+
+fn current_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/// Remember an accepted answer so that `cargo verify` can check it later on.
+fn store_answer(year: Option<Year>, day: Day, part: u8, answer: &str) {
+    match answers::store(year, day, part, answer) {
+        Ok(()) => println!("Stored answer for day {day}, part {part}."),
+        Err(e) => eprintln!("Failed to store answer: {e}"),
+    }
+}
+
+/// Whether the binary was invoked with `--json` and should emit one [`PartReport`] per part instead of human-readable output.
+fn is_json_output() -> bool {
+    env::args().any(|x| x == "--json")
 }
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///  2. with `--time`, the function is benched (approx. the configured budget, 1 second by default, or the minimum sample count, whatever takes longer.)
+///
+/// A panic of the first execution is caught and returned as an error message, the part is not benched then.
+/// Allocations are counted for the first execution only, see [`alloc_stats::measure`].
 fn run_timed<I: Copy, T>(
     func: impl Fn(I) -> T,
     input: I,
+    year: Option<Year>,
+    day: Day,
+    is_quiet: bool,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> (Result<T, String>, Statistics, Option<AllocStats>) {
     let timer = Instant::now();
-    let result = {
+    let (result, allocations) = {
         #[cfg(feature = "dhat-heap")]
         let _profiler = dhat::Profiler::new_heap();
 
-        func(input)
+        alloc_stats::measure(|| catch_panic(|| func(input)))
     };
     let base_time = timer.elapsed();
 
+    let result = match result {
+        Ok(result) => result,
+        Err(message) => {
+            return (
+                Err(message),
+                Statistics::from_single(base_time),
+                allocations,
+            )
+        }
+    };
+
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    let stats = if std::env::args().any(|x| x == "--time") {
+        let config = BenchConfig::from_args(year, day).unwrap_or_else(|e| {
+            eprintln!("Invalid bench config: {e}");
+            process::exit(1);
+        });
+        bench(func, input, &base_time, &config, is_quiet)
     } else {
-        (base_time, 1)
+        Statistics::from_single(base_time)
     };
 
-    (result, run.0, run.1)
+    (Ok(result), stats, allocations)
 }
 
-fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
+/// Run `func`, turning a panic into its message and location instead of printing it with a backtrace.
+fn catch_panic<T>(func: impl FnOnce() -> T) -> Result<T, String> {
+    let message = Arc::new(Mutex::new(None));
+    let hook_message = Arc::clone(&message);
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        *hook_message.lock().unwrap() = Some(info.to_string().replace('\n', " "));
+    }));
+    let result = panic::catch_unwind(AssertUnwindSafe(func));
+    panic::set_hook(default_hook);
 
-    let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+    result.map_err(|_| {
+        message
+            .lock()
+            .unwrap()
+            .take()
+            .unwrap_or_else(|| "panicked".into())
+    })
+}
 
-    let mut timers: Vec<Duration> = vec![];
+fn bench<I: Copy, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+    config: &BenchConfig,
+    is_quiet: bool,
+) -> Statistics {
+    if !is_quiet {
+        let mut stdout = stdout();
+        print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+        let _ = stdout.flush();
+    }
+
+    let bench_iterations = config.iterations(*base_time);
+
+    // warm up caches and branch predictors before collecting samples.
+    // the first run already did that for parts that take up the whole budget, another one would exceed it.
+    let warm_up_iterations = if *base_time >= config.budget {
+        0
+    } else {
+        (bench_iterations / 10).max(1)
+    };
+    for _ in 0..warm_up_iterations {
+        black_box(func(black_box(input)));
+    }
+
+    let mut timers: Vec<Duration> = Vec::with_capacity(bench_iterations as usize);
 
     for _ in 0..bench_iterations {
         let timer = Instant::now();
@@ -67,72 +277,73 @@ fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
-}
-
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+    Statistics::from_samples(&timers)
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
+pub(crate) fn format_duration(stats: &Statistics) -> String {
+    let mean = stats.mean_duration();
+    if stats.samples == 1 {
+        format!(" ({mean:.1?})")
     } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+        let ci95 = Duration::from_nanos(stats.ci95);
+        let samples = stats.samples;
+        format!(" ({mean:.1?} ± {ci95:.1?} @ {samples} samples)")
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+/// Format the allocations of a part to follow its duration, empty if they were not counted.
+pub(crate) fn format_allocations(allocations: Option<&AllocStats>) -> String {
+    allocations.map_or_else(String::new, |x| format!(" [{x}]"))
+}
+
+pub(crate) fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
+    if !is_intermediate_result {
+        print!("\r{}", format_result(result, part, duration_str));
+        return;
+    }
+
     match result {
         Some(result) => {
             if result.to_string().contains('\n') {
-                let str = format!("{part}: ▼ {duration_str}");
-                if is_intermediate_result {
-                    print!("{str}");
-                } else {
-                    print!("\r");
-                    println!("{str}");
-                    println!("{result}");
-                }
+                print!("{part}: ▼ ");
             } else {
-                let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}");
-                if is_intermediate_result {
-                    print!("{str}");
-                } else {
-                    print!("\r");
-                    println!("{str}");
-                }
+                print!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}");
             }
         }
         None => {
-            if is_intermediate_result {
-                print!("{part}: ✖");
+            print!("{part}: ✖");
+        }
+    }
+}
+
+/// Format the error of a part that failed, including a trailing newline.
+pub(crate) fn format_failure(day: Day, part: &str, error: &str) -> String {
+    format!("{part}: {ANSI_RED}✖ failed{ANSI_RESET} (day {day}): {error}\n")
+}
+
+/// Format the final result of a part, including a trailing newline.
+pub(crate) fn format_result<T: Display>(
+    result: &Option<T>,
+    part: &str,
+    duration_str: &str,
+) -> String {
+    match result {
+        Some(result) => {
+            let result = result.to_string();
+            if result.contains('\n') {
+                format!("{part}: ▼ {duration_str}\n{result}\n")
             } else {
-                print!("\r");
-                println!("{part}: ✖             ");
+                format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}\n")
             }
         }
+        None => format!("{part}: ✖             \n"),
     }
 }
 
-/// Parse the arguments passed to `solve` and try to submit one part of the solution if:
-///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
-    day: Day,
-    part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+/// Parse the part passed to `solve` with `--submit`, if any.
+fn submit_part() -> Option<u8> {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
@@ -146,20 +357,40 @@ fn submit_result<T: Display>(
 
     let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
 
-    let Ok(part_submit) = args[part_index].parse::<u8>() else {
+    let Some(Ok(part_submit)) = args.get(part_index).map(|x| x.parse::<u8>()) else {
         eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
         process::exit(1);
     };
 
-    if part_submit != part {
+    Some(part_submit)
+}
+
+/// Try to submit one part of the solution if `--submit` matches `part`.
+/// Answers that are known to fail, according to the submission history of the part, are not sent.
+fn submit_result(
+    answer: &str,
+    year: Option<Year>,
+    day: Day,
+    part: u8,
+) -> Option<Result<String, AocClientError>> {
+    if submit_part() != Some(part) {
+        return None;
+    }
+
+    let history = submissions::read(year, day, part);
+    if let Err(refusal) = submissions::guard(&history, answer, current_timestamp()) {
+        eprintln!("Refusing to submit {answer}: {refusal}");
         return None;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+    // stored answers may come from `verify --record`, which never submitted them.
+    if let Some(known_answer) = answers::read(year, day, part).filter(|x| x != answer) {
+        eprintln!("Note: {answer} differs from the stored answer {known_answer}.");
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    println!("Submitting result...");
+    Some(
+        aoc_client::client()
+            .and_then(|client| client.submit(aoc_client::resolve_year(year)?, day, part, answer)),
+    )
 }
@@ -0,0 +1,209 @@
+/// Summary statistics over the sample distribution of a benchmark.
+use std::{collections::HashMap, time::Duration};
+use tinyjson::JsonValue;
+
+/// z-score of a two-sided 95% confidence interval.
+const Z_95: f64 = 1.96;
+
+/// Samples further than this many interquartile ranges outside the quartiles are outliers (Tukey's fences).
+const OUTLIER_IQR_FACTOR: f64 = 1.5;
+
+/// Timing statistics for a solution part. All durations are in nanoseconds.
+///
+/// Outliers are detected with Tukey's fences and excluded from `mean`, `std_dev` and `ci95`,
+/// so that a single hiccup does not skew the reported time.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Statistics {
+    pub samples: u64,
+    pub outliers: u64,
+    pub mean: u64,
+    pub median: u64,
+    pub min: u64,
+    pub max: u64,
+    pub std_dev: u64,
+    /// Half-width of the 95% confidence interval of the mean.
+    pub ci95: u64,
+}
+
+impl Statistics {
+    /// Statistics of a single, un-benched execution.
+    pub fn from_single(duration: Duration) -> Self {
+        let nanos = duration_to_nanos(duration);
+        Self {
+            samples: 1,
+            outliers: 0,
+            mean: nanos,
+            median: nanos,
+            min: nanos,
+            max: nanos,
+            std_dev: 0,
+            ci95: 0,
+        }
+    }
+
+    /// # Panics
+    /// Panics if `samples` is empty.
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        assert!(!samples.is_empty(), "expected at least one sample");
+
+        let mut nanos: Vec<u64> = samples.iter().copied().map(duration_to_nanos).collect();
+        nanos.sort_unstable();
+
+        let q1 = percentile(&nanos, 0.25);
+        let q3 = percentile(&nanos, 0.75);
+        let iqr = q3 - q1;
+        let (low, high) = (q1 - OUTLIER_IQR_FACTOR * iqr, q3 + OUTLIER_IQR_FACTOR * iqr);
+
+        let inliers: Vec<f64> = nanos
+            .iter()
+            .map(|&x| x as f64)
+            .filter(|&x| x >= low && x <= high)
+            .collect();
+
+        let n = inliers.len() as f64;
+        let mean = inliers.iter().sum::<f64>() / n;
+        let variance = if inliers.len() > 1 {
+            inliers.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0)
+        } else {
+            0.0
+        };
+        let std_dev = variance.sqrt();
+
+        Self {
+            samples: nanos.len() as u64,
+            outliers: (nanos.len() - inliers.len()) as u64,
+            mean: mean.round() as u64,
+            median: percentile(&nanos, 0.5).round() as u64,
+            min: nanos[0],
+            max: nanos[nanos.len() - 1],
+            std_dev: std_dev.round() as u64,
+            ci95: (Z_95 * std_dev / n.sqrt()).round() as u64,
+        }
+    }
+
+    pub fn mean_duration(&self) -> Duration {
+        Duration::from_nanos(self.mean)
+    }
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn duration_to_nanos(duration: Duration) -> u64 {
+    duration.as_nanos().min(u128::from(u64::MAX)) as u64
+}
+
+/// Linearly interpolated percentile of sorted values, `p` in `0.0..=1.0`.
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+fn percentile(sorted: &[u64], p: f64) -> f64 {
+    let rank = p * (sorted.len() - 1) as f64;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+    let weight = rank - lower as f64;
+    sorted[lower] as f64 * (1.0 - weight) + sorted[upper] as f64 * weight
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Statistics> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &Statistics) -> Self {
+        let map: HashMap<String, JsonValue> = [
+            ("samples", value.samples),
+            ("outliers", value.outliers),
+            ("mean_nanos", value.mean),
+            ("median_nanos", value.median),
+            ("min_nanos", value.min),
+            ("max_nanos", value.max),
+            ("std_dev_nanos", value.std_dev),
+            ("ci95_nanos", value.ci95),
+        ]
+        .into_iter()
+        .map(|(key, x)| (key.to_string(), JsonValue::Number(x as f64)))
+        .collect();
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Statistics {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected statistics to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .map(|v| v as u64)
+                .ok_or(format!("Expected statistics.{key} to be a number."))
+        };
+
+        Ok(Statistics {
+            samples: number("samples")?,
+            outliers: number("outliers")?,
+            mean: number("mean_nanos")?,
+            median: number("median_nanos")?,
+            min: number("min_nanos")?,
+            max: number("max_nanos")?,
+            std_dev: number("std_dev_nanos")?,
+            ci95: number("ci95_nanos")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Statistics;
+    use std::time::Duration;
+
+    fn nanos(values: &[u64]) -> Vec<Duration> {
+        values.iter().copied().map(Duration::from_nanos).collect()
+    }
+
+    #[test]
+    fn computes_statistics() {
+        let stats = Statistics::from_samples(&nanos(&[10, 12, 11, 13, 14]));
+        assert_eq!(stats.samples, 5);
+        assert_eq!(stats.outliers, 0);
+        assert_eq!(stats.mean, 12);
+        assert_eq!(stats.median, 12);
+        assert_eq!(stats.min, 10);
+        assert_eq!(stats.max, 14);
+        assert_eq!(stats.std_dev, 2);
+        assert_eq!(stats.ci95, 1);
+    }
+
+    #[test]
+    fn excludes_outliers_from_mean() {
+        let stats = Statistics::from_samples(&nanos(&[10, 10, 11, 10, 11, 10, 1_000]));
+        assert_eq!(stats.outliers, 1);
+        assert_eq!(stats.mean, 10);
+        assert_eq!(stats.max, 1_000);
+    }
+
+    #[test]
+    fn handles_single_samples() {
+        let stats = Statistics::from_samples(&nanos(&[42]));
+        assert_eq!(stats, Statistics::from_single(Duration::from_nanos(42)));
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let stats = Statistics::from_samples(&nanos(&[10, 12, 11, 13, 14, 100]));
+        let json = tinyjson::JsonValue::from(&stats);
+        assert_eq!(Statistics::try_from(&json), Ok(stats));
+    }
+}
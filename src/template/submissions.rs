@@ -0,0 +1,510 @@
+/// History of answers submitted to the website, stored as one JSON record per line next to the answers,
+/// e.g. `data/answers/01-2.submissions.jsonl`. Used to refuse submissions that are known to fail.
+use std::{
+    collections::HashMap,
+    env,
+    fmt::Display,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::PathBuf,
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::aoc_client;
+use crate::template::{data_dir, Day, Year};
+
+/// The verdict of the website on a submitted answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint in which direction.
+    Wrong,
+    /// The answer was not checked because the previous submission was too recent.
+    RateLimited {
+        wait_seconds: Option<u64>,
+    },
+    /// The part is already solved or not unlocked yet.
+    WrongLevel,
+    Unknown,
+}
+
+impl Outcome {
+    /// Read the outcome from the response message of a submission.
+    pub fn from_message(message: &str) -> Self {
+        if aoc_client::is_correct_answer(message) {
+            Outcome::Correct
+        } else if message.contains("answer is too high") {
+            Outcome::TooHigh
+        } else if message.contains("answer is too low") {
+            Outcome::TooLow
+        } else if message.contains("not the right answer") {
+            Outcome::Wrong
+        } else if message.contains("answer too recently") {
+            Outcome::RateLimited {
+                wait_seconds: parse_wait(message),
+            }
+        } else if message.contains("solving the right level") {
+            Outcome::WrongLevel
+        } else {
+            Outcome::Unknown
+        }
+    }
+
+    fn is_rejection(self) -> bool {
+        matches!(self, Outcome::TooHigh | Outcome::TooLow | Outcome::Wrong)
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Outcome::Correct => "correct",
+            Outcome::TooHigh => "too_high",
+            Outcome::TooLow => "too_low",
+            Outcome::Wrong => "wrong",
+            Outcome::RateLimited { .. } => "rate_limited",
+            Outcome::WrongLevel => "wrong_level",
+            Outcome::Unknown => "unknown",
+        }
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "correct"),
+            Outcome::TooHigh => write!(f, "too high"),
+            Outcome::TooLow => write!(f, "too low"),
+            Outcome::Wrong => write!(f, "wrong"),
+            Outcome::RateLimited {
+                wait_seconds: Some(secs),
+            } => write!(f, "rate-limited for {secs}s"),
+            Outcome::RateLimited { wait_seconds: None } => write!(f, "rate-limited"),
+            Outcome::WrongLevel => write!(f, "already solved or locked"),
+            Outcome::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+/// Parse the wait time of a message like "You have 1m 5s left to wait."
+pub(crate) fn parse_wait(message: &str) -> Option<u64> {
+    let start = message.find("You have ")? + "You have ".len();
+    let end = start + message[start..].find(" left to wait")?;
+
+    message[start..end]
+        .split_whitespace()
+        .map(|part| {
+            let (value, unit) = part.split_at(part.find(|c: char| !c.is_ascii_digit())?);
+            let value: u64 = value.parse().ok()?;
+            match unit {
+                "h" => Some(value * 3_600),
+                "m" => Some(value * 60),
+                "s" => Some(value),
+                _ => None,
+            }
+        })
+        .sum()
+}
+
+/// Parse the cooldown after a wrong answer, e.g. "Please wait one minute before trying again."
+pub fn parse_cooldown(message: &str) -> Option<u64> {
+    let start = message.find("lease wait ")? + "lease wait ".len();
+    let end = start + message[start..].find(" before trying again")?;
+    let (count, unit) = message[start..end].split_once(' ')?;
+
+    let count: u64 = match count {
+        "a" | "an" | "one" => 1,
+        x => x.parse().ok()?,
+    };
+    let unit = match unit.trim_end_matches('s') {
+        "second" => 1,
+        "minute" => 60,
+        "hour" => 3_600,
+        _ => return None,
+    };
+    Some(count * unit)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A single submitted answer and the verdict of the website.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Submission {
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    pub answer: String,
+    pub outcome: Outcome,
+    /// Seconds the website asked to wait after a wrong answer. Rate limits carry their own wait.
+    pub wait_seconds: Option<u64>,
+}
+
+impl Submission {
+    /// Seconds to wait after this submission before the next one, if the website asked to.
+    fn cooldown(&self) -> Option<u64> {
+        match self.outcome {
+            Outcome::RateLimited { wait_seconds } => wait_seconds,
+            _ => self.wait_seconds,
+        }
+    }
+}
+
+fn get_path(year: Option<Year>, day: Day, part: u8) -> PathBuf {
+    env::current_dir()
+        .unwrap()
+        .join(data_dir(year))
+        .join("answers")
+        .join(format!("{day}-{part}.submissions.jsonl"))
+}
+
+/// Read all previous submissions of a part, oldest first. Lines that can't be parsed are skipped.
+pub fn read(year: Option<Year>, day: Day, part: u8) -> Vec<Submission> {
+    fs::read_to_string(get_path(year, day, part))
+        .map(|s| {
+            s.lines()
+                .filter_map(|line| JsonValue::from_str(line).ok())
+                .filter_map(|json| Submission::try_from(&json).ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Append a submission to the history of a part.
+pub fn record(
+    year: Option<Year>,
+    day: Day,
+    part: u8,
+    submission: &Submission,
+) -> Result<(), io::Error> {
+    let path = get_path(year, day, part);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let line = JsonValue::from(submission)
+        .stringify()
+        .expect("submission to be serializable");
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{line}")
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Why a submission was refused before sending it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Refusal {
+    AlreadySolved { answer: String },
+    KnownWrong { outcome: Outcome },
+    NotBelow { too_high: String },
+    NotAbove { too_low: String },
+    RateLimited { wait_seconds: u64 },
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::AlreadySolved { answer } => {
+                write!(f, "the part is already solved with answer {answer}.")
+            }
+            Refusal::KnownWrong { outcome } => {
+                write!(f, "this answer was already submitted and is {outcome}.")
+            }
+            Refusal::NotBelow { too_high } => {
+                write!(f, "{too_high} was already too high.")
+            }
+            Refusal::NotAbove { too_low } => {
+                write!(f, "{too_low} was already too low.")
+            }
+            Refusal::RateLimited { wait_seconds } => {
+                write!(f, "the website asked to wait another {wait_seconds}s.")
+            }
+        }
+    }
+}
+
+/// Check whether `answer` is worth submitting, given the history of a part.
+/// Only a recorded correct submission counts as solved, answers stored by `verify --record` were never sent.
+/// `now` is the current unix timestamp in seconds.
+pub fn guard(history: &[Submission], answer: &str, now: u64) -> Result<(), Refusal> {
+    if let Some(correct) = history.iter().find(|x| x.outcome == Outcome::Correct) {
+        return Err(Refusal::AlreadySolved {
+            answer: correct.answer.clone(),
+        });
+    }
+
+    if let Some(previous) = history
+        .iter()
+        .rev()
+        .find(|x| x.answer == answer && x.outcome.is_rejection())
+    {
+        return Err(Refusal::KnownWrong {
+            outcome: previous.outcome,
+        });
+    }
+
+    if let Ok(value) = answer.parse::<i128>() {
+        let bound = |outcome: Outcome| {
+            history
+                .iter()
+                .filter(move |x| x.outcome == outcome)
+                .filter_map(|x| Some((x.answer.parse::<i128>().ok()?, &x.answer)))
+        };
+
+        if let Some((_, too_high)) = bound(Outcome::TooHigh)
+            .filter(|(x, _)| value >= *x)
+            .min_by_key(|(x, _)| *x)
+        {
+            return Err(Refusal::NotBelow {
+                too_high: too_high.clone(),
+            });
+        }
+
+        if let Some((_, too_low)) = bound(Outcome::TooLow)
+            .filter(|(x, _)| value <= *x)
+            .max_by_key(|(x, _)| *x)
+        {
+            return Err(Refusal::NotAbove {
+                too_low: too_low.clone(),
+            });
+        }
+    }
+
+    if let Some((timestamp, wait_seconds)) = history
+        .last()
+        .and_then(|x| Some((x.timestamp, x.cooldown()?)))
+    {
+        let until = timestamp + wait_seconds;
+        if now < until {
+            return Err(Refusal::RateLimited {
+                wait_seconds: until - now,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Submission> for JsonValue {
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "outcome".into(),
+            JsonValue::String(value.outcome.as_str().into()),
+        );
+
+        #[allow(clippy::cast_precision_loss)]
+        let wait_seconds = match value.cooldown() {
+            Some(secs) => JsonValue::Number(secs as f64),
+            None => JsonValue::Null,
+        };
+        map.insert("wait_seconds".into(), wait_seconds);
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Submission {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected submission.timestamp to be a number.")?;
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.answer to be a string.")?;
+
+        let wait_seconds = json
+            .get("wait_seconds")
+            .and_then(|v| v.get::<f64>())
+            .map(|x| *x as u64);
+
+        let outcome = match json.get("outcome").and_then(|v| v.get::<String>()) {
+            Some(x) if x == "correct" => Outcome::Correct,
+            Some(x) if x == "too_high" => Outcome::TooHigh,
+            Some(x) if x == "too_low" => Outcome::TooLow,
+            Some(x) if x == "wrong" => Outcome::Wrong,
+            Some(x) if x == "rate_limited" => Outcome::RateLimited { wait_seconds },
+            Some(x) if x == "wrong_level" => Outcome::WrongLevel,
+            Some(x) if x == "unknown" => Outcome::Unknown,
+            _ => return Err("Expected submission.outcome to be a known outcome.".into()),
+        };
+
+        Ok(Submission {
+            timestamp: timestamp as u64,
+            answer: answer.clone(),
+            outcome,
+            wait_seconds: wait_seconds.filter(|_| !matches!(outcome, Outcome::RateLimited { .. })),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{guard, parse_cooldown, Outcome, Refusal, Submission};
+    use tinyjson::JsonValue;
+
+    fn submission(answer: &str, outcome: Outcome) -> Submission {
+        Submission {
+            timestamp: 100,
+            answer: answer.into(),
+            outcome,
+            wait_seconds: None,
+        }
+    }
+
+    #[test]
+    fn reads_outcomes() {
+        assert_eq!(
+            Outcome::from_message("That's the right answer! You are one gold star closer."),
+            Outcome::Correct
+        );
+        assert_eq!(
+            Outcome::from_message("That's not the right answer; your answer is too high. Please wait one minute before trying again."),
+            Outcome::TooHigh
+        );
+        assert_eq!(
+            Outcome::from_message("That's not the right answer. If you're stuck, make sure you're using the full input data."),
+            Outcome::Wrong
+        );
+        assert_eq!(
+            Outcome::from_message("You gave an answer too recently; you have to wait after submitting an answer before trying again. You have 1m 5s left to wait."),
+            Outcome::RateLimited {
+                wait_seconds: Some(65)
+            }
+        );
+        assert_eq!(
+            Outcome::from_message(
+                "You don't seem to be solving the right level. Did you already complete it?"
+            ),
+            Outcome::WrongLevel
+        );
+    }
+
+    #[test]
+    fn refuses_known_answers() {
+        let history = [
+            submission("10", Outcome::TooLow),
+            submission("abc", Outcome::Wrong),
+        ];
+        assert_eq!(
+            guard(&history, "abc", 100),
+            Err(Refusal::KnownWrong {
+                outcome: Outcome::Wrong
+            })
+        );
+        assert_eq!(
+            guard(&[submission("42", Outcome::Correct)], "43", 100),
+            Err(Refusal::AlreadySolved {
+                answer: "42".into()
+            })
+        );
+        assert_eq!(guard(&history, "abd", 100), Ok(()));
+    }
+
+    #[test]
+    fn refuses_answers_outside_bracket() {
+        let history = [
+            submission("10", Outcome::TooLow),
+            submission("50", Outcome::TooHigh),
+            submission("30", Outcome::TooHigh),
+            submission("5", Outcome::TooLow),
+        ];
+        assert_eq!(
+            guard(&history, "40", 100),
+            Err(Refusal::NotBelow {
+                too_high: "30".into()
+            })
+        );
+        assert_eq!(
+            guard(&history, "7", 100),
+            Err(Refusal::NotAbove {
+                too_low: "10".into()
+            })
+        );
+        assert_eq!(guard(&history, "20", 100), Ok(()));
+    }
+
+    #[test]
+    fn refuses_while_rate_limited() {
+        let history = [submission(
+            "1",
+            Outcome::RateLimited {
+                wait_seconds: Some(60),
+            },
+        )];
+        assert_eq!(
+            guard(&history, "2", 130),
+            Err(Refusal::RateLimited { wait_seconds: 30 })
+        );
+        assert_eq!(guard(&history, "2", 160), Ok(()));
+    }
+
+    #[test]
+    fn refuses_during_cooldown_after_wrong_answers() {
+        assert_eq!(
+            parse_cooldown(
+                "That's not the right answer. Please wait one minute before trying again."
+            ),
+            Some(60)
+        );
+        assert_eq!(
+            parse_cooldown(
+                "That's not the right answer; please wait 5 minutes before trying again."
+            ),
+            Some(300)
+        );
+        assert_eq!(parse_cooldown("That's the right answer!"), None);
+
+        let history = [Submission {
+            wait_seconds: Some(60),
+            ..submission("1", Outcome::TooLow)
+        }];
+        assert_eq!(
+            guard(&history, "2", 150),
+            Err(Refusal::RateLimited { wait_seconds: 10 })
+        );
+        assert_eq!(guard(&history, "2", 160), Ok(()));
+    }
+
+    #[test]
+    fn roundtrips_submissions() {
+        for (outcome, wait_seconds) in [
+            (Outcome::TooHigh, None),
+            (Outcome::TooLow, Some(60)),
+            (
+                Outcome::RateLimited {
+                    wait_seconds: Some(42),
+                },
+                None,
+            ),
+        ] {
+            let submission = Submission {
+                wait_seconds,
+                ..submission("123", outcome)
+            };
+            let json = JsonValue::from(&submission);
+            assert_eq!(Submission::try_from(&json), Ok(submission));
+        }
+    }
+}
@@ -1,9 +1,17 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{
+    collections::HashMap,
+    fs,
+    io::{Error, ErrorKind},
+    str::FromStr,
+};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::alloc_stats::AllocStats;
+use crate::template::bench_config::parse_duration;
+use crate::template::stats::Statistics;
+use crate::template::{data_dir, Day, Year};
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+static TIMINGS_FILE_NAME: &str = "timings.json";
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
@@ -11,33 +19,83 @@ pub struct Timing {
     pub day: Day,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
+    /// Full benchmark statistics, absent in timings stored by older versions.
+    pub part_1_stats: Option<Statistics>,
+    pub part_2_stats: Option<Statistics>,
+    /// Heap allocations per part, only present in timings benched with `--allocs`.
+    pub part_1_allocations: Option<AllocStats>,
+    pub part_2_allocations: Option<AllocStats>,
+    /// Parts that returned an error or panicked, as opposed to parts that are not implemented yet.
+    pub failed_parts: Vec<u8>,
     pub total_nanos: f64,
 }
 
+/// A single stored benchmark run of a day, kept to track performance over time.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HistoryEntry {
+    pub day: Day,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    /// Abbreviated hash of the git commit that was benched, if available.
+    pub commit: Option<String>,
+    /// Label of the machine the run was benched on, if available.
+    pub machine: Option<String>,
+    pub part_1_nanos: Option<f64>,
+    pub part_2_nanos: Option<f64>,
+}
+
+impl HistoryEntry {
+    pub fn new(
+        timing: &Timing,
+        timestamp: u64,
+        commit: Option<String>,
+        machine: Option<String>,
+    ) -> Self {
+        Self {
+            day: timing.day,
+            timestamp,
+            commit,
+            machine,
+            part_1_nanos: timing.part_nanos(1),
+            part_2_nanos: timing.part_nanos(2),
+        }
+    }
+}
+
 /// Represents benchmark times for a set of days.
 /// Can be serialized from / to JSON.
 #[derive(Clone, Debug, Default)]
 pub struct Timings {
+    /// Latest timing per day.
     pub data: Vec<Timing>,
+    /// Append-only log of every stored run.
+    pub history: Vec<HistoryEntry>,
 }
 
 impl Timings {
-    /// Dehydrate timings to a JSON file.
-    pub fn store_file(&self) -> Result<(), Error> {
+    /// Dehydrate timings to the JSON file of `year`, e.g. `data/timings.json` or `data/2023/timings.json`.
+    pub fn store_file(&self, year: Option<Year>) -> Result<(), Error> {
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        let dir = data_dir(year);
+        fs::create_dir_all(&dir)?;
+        let mut file = fs::File::create(dir.join(TIMINGS_FILE_NAME))?;
         json.format_to(&mut file)
     }
 
-    /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        fs::read_to_string(TIMINGS_FILE_PATH)
-            .map_err(|x| x.to_string())
-            .and_then(Timings::try_from)
-            .unwrap_or_default()
+    /// Rehydrate timings from the JSON file of `year`. If not present, returns empty timings.
+    /// A file that can't be read or parsed is an error, so that its history is not overwritten.
+    pub fn read_from_file(year: Option<Year>) -> Result<Self, String> {
+        let path = data_dir(year).join(TIMINGS_FILE_NAME);
+        match fs::read_to_string(&path) {
+            Ok(content) => Timings::try_from(content),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Timings::default()),
+            Err(e) => Err(e.to_string()),
+        }
+        .map_err(|e| format!("Failed to read \"{}\": {e}", path.display()))
     }
 
     /// Merge two sets of timings, overwriting `self` with `other` if present.
+    /// Histories are concatenated, `new` entries last.
     pub fn merge(&self, new: &Self) -> Self {
         let mut data: Vec<Timing> = vec![];
 
@@ -51,8 +109,35 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
-        Timings { data }
+        data.sort_unstable_by_key(|a| a.day);
+
+        let history = self.history.iter().chain(&new.history).cloned().collect();
+
+        Timings { data, history }
+    }
+
+    /// Append a history entry for every timing in `self`.
+    pub fn record_history(&mut self, timestamp: u64, commit: Option<&str>, machine: Option<&str>) {
+        let entries: Vec<HistoryEntry> = self
+            .data
+            .iter()
+            .map(|t| {
+                HistoryEntry::new(
+                    t,
+                    timestamp,
+                    commit.map(Into::into),
+                    machine.map(Into::into),
+                )
+            })
+            .collect();
+        self.history.extend(entries);
+    }
+
+    /// History entries of a single day in chronological order.
+    pub fn history_for_day(&self, day: Day) -> Vec<&HistoryEntry> {
+        let mut entries: Vec<_> = self.history.iter().filter(|e| e.day == day).collect();
+        entries.sort_by_key(|e| e.timestamp);
+        entries
     }
 
     /// Sum up total duration of timings as millis.
@@ -60,6 +145,10 @@ impl Timings {
         self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
     }
 
+    pub fn get_day(&self, day: Day) -> Option<&Timing> {
+        self.data.iter().find(|t| t.day == day)
+    }
+
     pub fn is_day_complete(&self, day: Day) -> bool {
         self.data
             .iter()
@@ -67,6 +156,26 @@ impl Timings {
     }
 }
 
+impl Timing {
+    /// Mean execution time of a part in nanoseconds.
+    /// Falls back to parsing the formatted timing for entries stored without statistics.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn part_nanos(&self, part: u8) -> Option<f64> {
+        let (timing, stats) = match part {
+            1 => (&self.part_1, &self.part_1_stats),
+            2 => (&self.part_2, &self.part_2_stats),
+            _ => return None,
+        };
+
+        match stats {
+            Some(stats) => Some(stats.mean as f64),
+            None => parse_duration(timing.as_deref()?)
+                .ok()
+                .map(|d| d.as_nanos() as f64),
+        }
+    }
+}
+
 /* -------------------------------------------------------------------------- */
 
 impl From<Timings> for JsonValue {
@@ -78,6 +187,11 @@ impl From<Timings> for JsonValue {
             JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
         );
 
+        map.insert(
+            "history".into(),
+            JsonValue::Array(value.history.iter().map(JsonValue::from).collect()),
+        );
+
         JsonValue::Object(map)
     }
 }
@@ -88,19 +202,33 @@ impl TryFrom<String> for Timings {
     fn try_from(value: String) -> Result<Self, Self::Error> {
         let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
 
-        let json_data = json
+        let json = json
             .get::<HashMap<String, JsonValue>>()
-            .ok_or("expected JSON document to be an object.")?
+            .ok_or("expected JSON document to be an object.")?;
+
+        let json_data = json
             .get("data")
             .ok_or("expected JSON document to have key `data`.")?
             .get::<Vec<JsonValue>>()
             .ok_or("expected `json.data` to be an array.")?;
 
+        // NOTE: files written before history was tracked only have the `data` key.
+        let history = match json.get("history") {
+            None => vec![],
+            Some(v) => v
+                .get::<Vec<JsonValue>>()
+                .ok_or("expected `json.history` to be an array.")?
+                .iter()
+                .map(HistoryEntry::try_from)
+                .collect::<Result<_, _>>()?,
+        };
+
         Ok(Timings {
             data: json_data
                 .iter()
                 .map(Timing::try_from)
                 .collect::<Result<_, _>>()?,
+            history,
         })
     }
 }
@@ -133,6 +261,39 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        for (key, stats) in [
+            ("part_1_stats", &value.part_1_stats),
+            ("part_2_stats", &value.part_2_stats),
+        ] {
+            map.insert(
+                key.into(),
+                match stats {
+                    Some(x) => JsonValue::from(x),
+                    None => JsonValue::Null,
+                },
+            );
+        }
+
+        for (key, allocations) in [
+            ("part_1_allocations", &value.part_1_allocations),
+            ("part_2_allocations", &value.part_2_allocations),
+        ] {
+            if let Some(x) = allocations {
+                map.insert(key.into(), JsonValue::from(x));
+            }
+        }
+
+        map.insert(
+            "failed_parts".into(),
+            JsonValue::Array(
+                value
+                    .failed_parts
+                    .iter()
+                    .map(|x| JsonValue::Number(f64::from(*x)))
+                    .collect(),
+            ),
+        );
+
         JsonValue::Object(map)
     }
 }
@@ -166,15 +327,142 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        let part_1_stats = parse_optional_stats(json, "part_1_stats")?;
+        let part_2_stats = parse_optional_stats(json, "part_2_stats")?;
+        let part_1_allocations = parse_optional_allocations(json, "part_1_allocations")?;
+        let part_2_allocations = parse_optional_allocations(json, "part_2_allocations")?;
+
+        // NOTE: timings stored before failures were tracked don't have this key.
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let failed_parts = match json.get("failed_parts") {
+            Some(v) => v
+                .get::<Vec<JsonValue>>()
+                .ok_or("Expected timing.failed_parts to be an array.")?
+                .iter()
+                .map(|x| x.get::<f64>().map(|x| *x as u8))
+                .collect::<Option<_>>()
+                .ok_or("Expected timing.failed_parts to contain numbers.")?,
+            None => vec![],
+        };
+
         Ok(Timing {
             day,
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
+            part_1_stats,
+            part_2_stats,
+            part_1_allocations,
+            part_2_allocations,
+            failed_parts,
             total_nanos,
         })
     }
 }
 
+impl From<&HistoryEntry> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &HistoryEntry) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        let optional_string = |x: &Option<String>| match x {
+            Some(x) => JsonValue::String(x.clone()),
+            None => JsonValue::Null,
+        };
+        let optional_number = |x: Option<f64>| match x {
+            Some(x) => JsonValue::Number(x),
+            None => JsonValue::Null,
+        };
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+        map.insert("commit".into(), optional_string(&value.commit));
+        map.insert("machine".into(), optional_string(&value.machine));
+        map.insert("part_1_nanos".into(), optional_number(value.part_1_nanos));
+        map.insert("part_2_nanos".into(), optional_number(value.part_2_nanos));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for HistoryEntry {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected history entry to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected history.day to be a Day struct.")?;
+
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected history.timestamp to be a number.")?;
+
+        let optional_string = |key: &str| match json.get(key) {
+            Some(v) if !v.is_null() => v
+                .get::<String>()
+                .cloned()
+                .map(Some)
+                .ok_or(format!("Expected history.{key} to be null or string.")),
+            _ => Ok(None),
+        };
+        let optional_number = |key: &str| match json.get(key) {
+            Some(v) if !v.is_null() => v
+                .get::<f64>()
+                .copied()
+                .map(Some)
+                .ok_or(format!("Expected history.{key} to be null or a number.")),
+            _ => Ok(None),
+        };
+
+        Ok(HistoryEntry {
+            day,
+            timestamp: timestamp as u64,
+            commit: optional_string("commit")?,
+            machine: optional_string("machine")?,
+            part_1_nanos: optional_number("part_1_nanos")?,
+            part_2_nanos: optional_number("part_2_nanos")?,
+        })
+    }
+}
+
+/// Statistics keys are optional so timings files written before they existed stay readable.
+fn parse_optional_stats(
+    json: &HashMap<String, JsonValue>,
+    key: &str,
+) -> Result<Option<Statistics>, String> {
+    match json.get(key) {
+        None => Ok(None),
+        Some(v) if v.is_null() => Ok(None),
+        Some(v) => Statistics::try_from(v)
+            .map(Some)
+            .map_err(|e| format!("Expected timing.{key} to be null or statistics: {e}")),
+    }
+}
+
+/// Allocations are only stored for timings benched with `--allocs`.
+fn parse_optional_allocations(
+    json: &HashMap<String, JsonValue>,
+    key: &str,
+) -> Result<Option<AllocStats>, String> {
+    match json.get(key) {
+        None => Ok(None),
+        Some(v) if v.is_null() => Ok(None),
+        Some(v) => AllocStats::try_from(v)
+            .map(Some)
+            .map_err(|e| format!("Expected timing.{key} to be null or allocations: {e}")),
+    }
+}
+
 /* -------------------------------------------------------------------------- */
 
 #[cfg(feature = "test_lib")]
@@ -185,23 +473,39 @@ mod tests {
 
     fn get_mock_timings() -> Timings {
         Timings {
+            history: vec![],
             data: vec![
                 Timing {
                     day: day!(1),
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_allocations: None,
+                    part_2_allocations: None,
+                    failed_parts: vec![],
                     total_nanos: 3e+10,
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_allocations: None,
+                    part_2_allocations: None,
+                    failed_parts: vec![],
                     total_nanos: 7e+10,
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_allocations: None,
+                    part_2_allocations: None,
+                    failed_parts: vec![],
                     total_nanos: 4e+10,
                 },
             ],
@@ -223,11 +527,37 @@ mod tests {
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
         }
 
+        #[test]
+        fn handles_json_timings_with_stats() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000, "part_1_stats": { "samples": 10, "outliers": 1, "mean_nanos": 1000000, "median_nanos": 990000, "min_nanos": 900000, "max_nanos": 2000000, "std_dev_nanos": 5000, "ci95_nanos": 3100 }, "part_2_stats": null }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            let stats = timing.part_1_stats.as_ref().unwrap();
+            assert_eq!(stats.median, 990_000);
+            assert_eq!(stats.outliers, 1);
+            assert_eq!(timing.part_2_stats, None);
+        }
+
         #[test]
         fn handles_empty_timings() {
             let json = r#"{ "data": [] }"#.to_string();
             let timings = Timings::try_from(json).unwrap();
             assert_eq!(timings.data.len(), 0);
+            assert_eq!(timings.history.len(), 0);
+        }
+
+        #[test]
+        fn handles_json_history() {
+            let json = r#"{ "data": [], "history": [{ "day": "06", "timestamp": 1733500000, "commit": "abc1234", "machine": null, "part_1_nanos": 5400000, "part_2_nanos": null }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.history.len(), 1);
+            let entry = timings.history.first().unwrap();
+            assert_eq!(entry.day, day!(6));
+            assert_eq!(entry.timestamp, 1_733_500_000);
+            assert_eq!(entry.commit, Some("abc1234".to_string()));
+            assert_eq!(entry.machine, None);
+            assert_eq!(entry.part_1_nanos, Some(5_400_000_f64));
+            assert_eq!(entry.part_2_nanos, None);
         }
 
         #[test]
@@ -247,9 +577,37 @@ mod tests {
 
     mod serialization {
         use super::get_mock_timings;
+        use crate::template::{alloc_stats::AllocStats, timings::Timings};
         use std::collections::HashMap;
         use tinyjson::JsonValue;
 
+        #[test]
+        fn roundtrips_history() {
+            let mut timings = get_mock_timings();
+            timings.record_history(1_733_500_000, Some("abc1234"), Some("laptop"));
+            let json = JsonValue::from(timings.clone()).stringify().unwrap();
+            let parsed = Timings::try_from(json).unwrap();
+            assert_eq!(parsed.history, timings.history);
+            assert_eq!(parsed.history.len(), 3);
+        }
+
+        #[test]
+        fn roundtrips_allocations() {
+            let mut timings = get_mock_timings();
+            timings.data[0].part_2_allocations = Some(AllocStats {
+                allocations: 3,
+                bytes: 276,
+                peak_bytes: 232,
+            });
+            let json = JsonValue::from(timings.clone()).stringify().unwrap();
+            let parsed = Timings::try_from(json).unwrap();
+            assert_eq!(parsed.data[0].part_1_allocations, None);
+            assert_eq!(
+                parsed.data[0].part_2_allocations,
+                timings.data[0].part_2_allocations
+            );
+        }
+
         #[test]
         fn serializes_timings() {
             let timings = get_mock_timings();
@@ -277,43 +635,98 @@ mod tests {
         #[test]
         fn handles_completed_days() {
             let timings = Timings {
+                history: vec![],
                 data: vec![Timing {
                     day: day!(1),
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_allocations: None,
+                    part_2_allocations: None,
+                    failed_parts: vec![],
                     total_nanos: 3_000_000_000_f64,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
         fn handles_partial_days() {
             let timings = Timings {
+                history: vec![],
                 data: vec![Timing {
                     day: day!(1),
                     part_1: Some("1ms".into()),
                     part_2: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_allocations: None,
+                    part_2_allocations: None,
+                    failed_parts: vec![],
                     total_nanos: 1_000_000_000_f64,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
         fn handles_uncompleted_days() {
             let timings = Timings {
+                history: vec![],
                 data: vec![Timing {
                     day: day!(1),
                     part_1: None,
                     part_2: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_allocations: None,
+                    part_2_allocations: None,
+                    failed_parts: vec![],
                     total_nanos: 0.0,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
+        }
+    }
+
+    mod part_nanos {
+        use crate::{
+            day,
+            template::{
+                stats::Statistics,
+                timings::{Timing, Timings},
+            },
+        };
+
+        #[test]
+        fn prefers_statistics() {
+            let timings = Timings {
+                history: vec![],
+                data: vec![Timing {
+                    day: day!(1),
+                    part_1: Some("1.5ms".into()),
+                    part_2: Some("20.0µs".into()),
+                    part_1_stats: Some(Statistics {
+                        mean: 1_400_000,
+                        ..Statistics::default()
+                    }),
+                    part_2_stats: None,
+                    part_1_allocations: None,
+                    part_2_allocations: None,
+                    failed_parts: vec![],
+                    total_nanos: 1_420_000_f64,
+                }],
+            };
+
+            let timing = timings.get_day(day!(1)).unwrap();
+            assert_eq!(timing.part_nanos(1), Some(1_400_000_f64));
+            assert_eq!(timing.part_nanos(2), Some(20_000_f64));
+            assert_eq!(timing.part_nanos(3), None);
+            assert_eq!(timings.get_day(day!(2)).is_none(), true);
         }
     }
 
@@ -329,10 +742,16 @@ mod tests {
         fn handles_disjunct_timings() {
             let timings = get_mock_timings();
             let other = Timings {
+                history: vec![],
                 data: vec![Timing {
                     day: day!(3),
                     part_1: None,
                     part_2: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_allocations: None,
+                    part_2_allocations: None,
+                    failed_parts: vec![],
                     total_nanos: 0_f64,
                 }],
             };
@@ -349,10 +768,16 @@ mod tests {
             let timings = get_mock_timings();
 
             let other = Timings {
+                history: vec![],
                 data: vec![Timing {
                     day: day!(2),
                     part_1: None,
                     part_2: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_allocations: None,
+                    part_2_allocations: None,
+                    failed_parts: vec![],
                     total_nanos: 0_f64,
                 }],
             };
@@ -373,6 +798,23 @@ mod tests {
             assert_eq!(merged.data.len(), 3);
         }
 
+        #[test]
+        fn appends_history() {
+            let mut timings = get_mock_timings();
+            timings.record_history(2, None, None);
+            let mut other = get_mock_timings();
+            other.record_history(1, None, None);
+
+            let merged = timings.merge(&other);
+            assert_eq!(merged.history.len(), 6);
+            assert_eq!(merged.history[0].timestamp, 2);
+            assert_eq!(merged.history[5].timestamp, 1);
+
+            let day_history = merged.history_for_day(day!(1));
+            assert_eq!(day_history.len(), 2);
+            assert_eq!(day_history[0].timestamp, 1);
+        }
+
         #[test]
         fn handles_empty_other_timings() {
             let timings = get_mock_timings();
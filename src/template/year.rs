@@ -0,0 +1,154 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use crate::template::Day;
+
+/// The year of the first advent of code event.
+const FIRST_YEAR: u16 = 2015;
+
+/// A valid year of advent of code (i.e. 2015 or later).
+///
+/// The default year, set with `AOC_YEAR` in `.cargo/config.toml` at build time, uses a flat layout:
+/// `data/inputs/01.txt` and `src/bin/01.rs`. Any other year is scoped into its own
+/// directories: `data/2023/inputs/01.txt` and `src/bin/2023_01.rs`.
+///
+/// # Display
+/// This value displays as a four digit number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Year(u16);
+
+impl Year {
+    /// Creates a [`Year`] from the provided value if it's in the valid range,
+    /// returns [`None`] otherwise.
+    pub fn new(year: u16) -> Option<Self> {
+        if year < FIRST_YEAR {
+            return None;
+        }
+        Some(Self(year))
+    }
+
+    // Not part of the public API
+    #[doc(hidden)]
+    pub const fn __new_unchecked(year: u16) -> Self {
+        Self(year)
+    }
+
+    /// Converts the [`Year`] into an [`u16`].
+    pub fn into_inner(self) -> u16 {
+        self.0
+    }
+
+    /// The default year, whose files use the flat layout.
+    ///
+    /// Read from `AOC_YEAR` when the crate is built, so that the unlabeled files of the flat
+    /// layout can't be pointed at another year by the environment of a single run.
+    pub fn default_year() -> Option<Self> {
+        option_env!("AOC_YEAR")?.parse().ok()
+    }
+
+    /// Resolves a requested year to the one whose files are scoped, [`None`] for the default year.
+    pub fn scoped(year: Option<Self>) -> Option<Self> {
+        year.filter(|year| Some(*year) != Self::default_year())
+    }
+
+    /// Resolves a requested year to an actual year, falling back to the default year.
+    pub fn or_default(year: Option<Self>) -> Option<Self> {
+        year.or_else(Self::default_year)
+    }
+}
+
+impl Display for Year {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04}", self.0)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for Year {
+    type Err = YearFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let year = s.trim().parse().map_err(|_| YearFromStrError)?;
+        Self::new(year).ok_or(YearFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Year`].
+#[derive(Debug)]
+pub struct YearFromStrError;
+
+impl Error for YearFromStrError {}
+
+impl Display for YearFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "expecting a year of {FIRST_YEAR} or later")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The directory that holds the data files of `year`, e.g. `data` or `data/2023`.
+pub fn data_dir(year: Option<Year>) -> PathBuf {
+    let dir = PathBuf::from("data");
+    match Year::scoped(year) {
+        Some(year) => dir.join(year.to_string()),
+        None => dir,
+    }
+}
+
+/// The name of the solution binary of a day, e.g. `01` or `2023_01`.
+pub fn bin_name(year: Option<Year>, day: Day) -> String {
+    match Year::scoped(year) {
+        Some(year) => format!("{year}_{day}"),
+        None => day.to_string(),
+    }
+}
+
+/// The path of the solution binary of a day, e.g. `./src/bin/01.rs`.
+pub fn bin_path(year: Option<Year>, day: Day) -> String {
+    format!("./src/bin/{}.rs", bin_name(year, day))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Creates a [`Year`] value in a const context.
+#[macro_export]
+macro_rules! year {
+    ($year:expr) => {{
+        const _ASSERT: () = assert!(
+            $year >= 2015,
+            concat!("invalid year `", $year, "`, expecting 2015 or later"),
+        );
+        $crate::template::Year::__new_unchecked($year)
+    }};
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{bin_name, data_dir, Year};
+    use crate::day;
+    use std::path::PathBuf;
+
+    #[test]
+    fn parses_years() {
+        assert_eq!("2023".parse::<Year>().unwrap(), year!(2023));
+        assert!("2014".parse::<Year>().is_err());
+        assert!("23".parse::<Year>().is_err());
+    }
+
+    #[test]
+    fn scopes_paths_of_other_years() {
+        // NOTE: `AOC_YEAR` is set to 2024 in `.cargo/config.toml`.
+        assert_eq!(Year::default_year(), Some(year!(2024)));
+        assert_eq!(bin_name(None, day!(1)), "01");
+        assert_eq!(bin_name(Some(year!(2024)), day!(1)), "01");
+        assert_eq!(bin_name(Some(year!(2023)), day!(1)), "2023_01");
+        assert_eq!(data_dir(None), PathBuf::from("data"));
+        assert_eq!(data_dir(Some(year!(2023))), PathBuf::from("data/2023"));
+    }
+}
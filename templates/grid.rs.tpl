@@ -0,0 +1,37 @@
+// Day %DAY%: %TITLE%
+advent_of_code::solution!(%DAY_NUMBER%%YEAR_ARGUMENT%);
+
+use advent_of_code::grid::Grid;
+
+/// The input as a grid of characters, indexed by `grid[(x, y)]`.
+/// [`None`] if the input is not a grid, e.g. an empty example that was not filled in yet.
+fn parse(input: &str) -> Option<Grid<char>> {
+    Grid::parse_chars(input).ok()
+}
+
+pub fn part_one(input: &str) -> Option<%ANSWER_TYPE%> {
+    let _grid = parse(input)?;
+    None
+}
+
+pub fn part_two(input: &str) -> Option<%ANSWER_TYPE%> {
+    let _grid = parse(input)?;
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_year_file("examples", YEAR, DAY));
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_year_file("examples", YEAR, DAY));
+        assert_eq!(result, None);
+    }
+}